@@ -1,104 +0,0 @@
-"Deku Tree" {
-    savewarp: "Deku Tree",
-    time_of_day: None,
-    exits: {
-        "Kokiri Forest": true, //TODO separate region for Kokiri Forest near Deku Tree
-        "Queen Gohma Boss Room": true, //TODO required items
-    },
-}
-
-"Dodongos Cavern" {
-    savewarp: "Dodongos Cavern",
-    time_of_day: None,
-    exits: {
-        "Death Mountain Trail": true,
-        "King Dodongo Boss Room": true, //TODO item requirements
-    },
-}
-
-"Jabu Jabus Belly" {
-    savewarp: "Jabu Jabus Belly",
-    time_of_day: None,
-    exits: {
-        "Zoras Fountain": true,
-        "Barinade Boss Room": true, //TODO item requirements
-    },
-}
-
-"Forest Temple" {
-    savewarp: "Forest Temple",
-    time_of_day: None,
-    exits: {
-        "Sacred Forest Meadow": true,
-        "Phantom Ganon Boss Room": true, //TODO
-    },
-}
-
-"Fire Temple" {
-    savewarp: "Fire Temple",
-    time_of_day: None,
-    exits: {
-        "Death Mountain Crater": true, //TODO DMC point-to-point logic with health logic
-        "Volvagia Boss Room": true, //TODO item requirements
-    },
-}
-
-"Water Temple" {
-    savewarp: "Water Temple",
-    time_of_day: None,
-    exits: {
-        "Lake Hylia": true,
-        "Morpha Boss Room": is_adult, //TODO item requirements
-    },
-}
-
-"Shadow Temple" {
-    savewarp: "Shadow Temple",
-    time_of_day: None,
-    exits: {
-        "Graveyard": true,
-        "Bongo Bongo Boss Room": is_adult, //TODO item requirements
-    },
-}
-
-"Spirit Temple" {
-    savewarp: "Spirit Temple",
-    time_of_day: None,
-    exits: {
-        "Desert Colossus": true, //TODO separate exits for Requiem check exit and hands
-        "Twinrova Boss Room": is_adult, //TODO item requirements
-    },
-}
-
-"Ice Cavern" {
-    savewarp: "Ice Cavern",
-    time_of_day: None,
-    exits: {
-        "Zoras Fountain": true,
-    },
-}
-
-"Bottom of the Well" {
-    savewarp: "Bottom of the Well",
-    time_of_day: None,
-    exits: {
-        "Kakariko Village": true,
-    },
-}
-
-"Gerudo Training Ground" {
-    savewarp: "Gerudo Training Ground",
-    time_of_day: None,
-    exits: {
-        "Gerudo Fortress": true,
-    },
-}
-
-"Inside Ganons Castle" {
-    savewarp: "Inside Ganons Castle",
-    time_of_day: None,
-    exits: {
-        "Castle Grounds": true, //TODO require rainbow bridge, add separate region for castle grounds from Ganon's Castle
-        "Ganons Tower": true, //TODO require trials
-    },
-}
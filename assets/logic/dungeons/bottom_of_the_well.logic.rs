@@ -0,0 +1,6 @@
+"Bottom of the Well" {
+    time_of_day: None,
+    exits: {
+        "Kakariko Village": true,
+    },
+}
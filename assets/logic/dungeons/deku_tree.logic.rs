@@ -0,0 +1,23 @@
+"Deku Tree" {
+    time_of_day: None,
+    exits: {
+        "Kokiri Forest": true, //TODO separate region for Kokiri Forest near Deku Tree
+        "Queen Gohma Boss Room": true, //TODO required items
+    },
+}
+
+"Queen Gohma Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Deku Tree Queen Gohma Heart": true, //TODO items required to defeat Gohma
+        "Queen Gohma": true, //TODO items required to defeat Gohma
+    },
+    events: {
+        "Deku Tree Clear": true, //TODO items required to defeat Gohma
+    },
+    exits: {
+        "Deku Tree": true,
+        "Kokiri Forest": true, //TODO items required to defeat Gohma, separate region for Kokiri Forest near Deku Tree
+    },
+}
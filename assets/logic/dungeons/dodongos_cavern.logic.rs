@@ -0,0 +1,20 @@
+"Dodongos Cavern" {
+    time_of_day: None,
+    exits: {
+        "Death Mountain Trail": true,
+        "King Dodongo Boss Room": true, //TODO item requirements
+    },
+}
+
+"King Dodongo Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Dodongos Cavern King Dodongo Heart": true, //TODO item requirements
+        "King Dodongo": true, //TODO item requirements
+    },
+    exits: {
+        "Dodongos Cavern": true,
+        "Death Mountain Trail": true, //TODO item requirements
+    },
+}
@@ -0,0 +1,21 @@
+"Fire Temple" {
+    time_of_day: None,
+    exits: {
+        "Death Mountain Crater": true, //TODO DMC point-to-point logic
+        // The way to the boss room deals damage from fire and lava, and its heat requires the Goron Tunic, even if the temple was entered from elsewhere with dungeon entrance shuffle.
+        "Volvagia Boss Room": { access: can_use(GoronTunic), damage: 2 }, //TODO item requirements
+    },
+}
+
+"Volvagia Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Fire Temple Volvagia Heart": true, //TODO item requirements
+        "Volvagia": true, //TODO item requirements
+    },
+    exits: {
+        "Fire Temple": false,
+        "Death Mountain Crater": true, //TODO item requirements, DMC point-to-point logic
+    },
+}
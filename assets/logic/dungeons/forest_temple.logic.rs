@@ -0,0 +1,20 @@
+"Forest Temple" {
+    time_of_day: None,
+    exits: {
+        "Sacred Forest Meadow": true,
+        "Phantom Ganon Boss Room": true, //TODO
+    },
+}
+
+"Phantom Ganon Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Forest Temple Phantom Ganon Heart": true, //TODO item requirements
+        "Phantom Ganon": true, //TODO item requirements
+    },
+    exits: {
+        "Forest Temple": false,
+        "Sacred Forest Meadow": true, //TODO item requirements, patch exit in ER
+    },
+}
@@ -0,0 +1,13 @@
+"Ganons Tower" {
+    time_of_day: None,
+    farores_wind: false, // disabled in Ganon's Tower
+    exits: {
+        "Inside Ganons Castle": true,
+        "Ganondorf Boss Room": true, //TODO require Ganon boss key
+    },
+}
+
+"Ganondorf Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+}
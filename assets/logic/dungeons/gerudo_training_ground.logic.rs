@@ -0,0 +1,6 @@
+"Gerudo Training Ground" {
+    time_of_day: None,
+    exits: {
+        "Gerudo Fortress": true,
+    },
+}
@@ -0,0 +1,6 @@
+"Ice Cavern" {
+    time_of_day: None,
+    exits: {
+        "Zoras Fountain": true,
+    },
+}
@@ -0,0 +1,7 @@
+"Inside Ganons Castle" {
+    time_of_day: None,
+    exits: {
+        "Castle Grounds": true, //TODO require rainbow bridge, add separate region for castle grounds from Ganon's Castle
+        "Ganons Tower": true, //TODO require trials
+    },
+}
@@ -0,0 +1,20 @@
+"Jabu Jabus Belly" {
+    time_of_day: None,
+    exits: {
+        "Zoras Fountain": true,
+        "Barinade Boss Room": true, //TODO item requirements
+    },
+}
+
+"Barinade Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Jabu Jabus Belly Barinade Heart": true, //TODO item requirements
+        "Barinade": true, //TODO item requirements
+    },
+    exits: {
+        "Jabu Jabus Belly": false,
+        "Zoras Fountain": is_child, //TODO item requirements
+    },
+}
@@ -0,0 +1,20 @@
+"Shadow Temple" {
+    time_of_day: None,
+    exits: {
+        "Graveyard": true,
+        "Bongo Bongo Boss Room": is_adult, //TODO item requirements
+    },
+}
+
+"Bongo Bongo Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Shadow Temple Bongo Bongo Heart": true, //TODO item requirements
+        "Bongo Bongo": true, //TODO item requirements
+    },
+    exits: {
+        "Shadow Temple": false,
+        "Graveyard": true, //TODO item requirements, separate region for Nocturne warp pad
+    },
+}
@@ -0,0 +1,20 @@
+"Spirit Temple" {
+    time_of_day: None,
+    exits: {
+        "Desert Colossus": true, //TODO separate exits for Requiem check exit and hands
+        "Twinrova Boss Room": is_adult, //TODO item requirements
+    },
+}
+
+"Twinrova Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Spirit Temple Twinrova Heart": true, //TODO item requirements
+        "Twinrova": true, //TODO item requirements
+    },
+    exits: {
+        "Spirit Temple": false,
+        "Desert Colossus": is_adult, //TODO item requirements
+    },
+}
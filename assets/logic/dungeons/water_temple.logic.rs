@@ -0,0 +1,20 @@
+"Water Temple" {
+    time_of_day: None,
+    exits: {
+        "Lake Hylia": true,
+        "Morpha Boss Room": is_adult, //TODO item requirements
+    },
+}
+
+"Morpha Boss Room" {
+    time_of_day: None,
+    farores_wind: false, // disabled in boss rooms
+    locations: {
+        "Water Temple Morpha Heart": true, //TODO item requirements
+        "Morpha": true, //TODO item requirements
+    },
+    exits: {
+        "Water Temple": false,
+        "Lake Hylia": true, //TODO item/trick requirements
+    },
+}
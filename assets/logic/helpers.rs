@@ -0,0 +1,39 @@
+// Helper functions that can be called from access expressions in the logic files.
+// These are expanded at compile time, so a helper's parameters can be items, events, or entire access expressions.
+
+// item aliases for progressive items
+fn Hookshot() { ProgressiveHookshot(1) }
+fn Longshot() { ProgressiveHookshot(2) }
+fn GoronBracelet() { ProgressiveStrengthUpgrade(1) }
+fn SilverGauntlets() { ProgressiveStrengthUpgrade(2) }
+fn GoldenGauntlets() { ProgressiveStrengthUpgrade(3) }
+fn SilverScale() { ProgressiveScale(1) }
+fn GoldenScale() { ProgressiveScale(2) }
+
+fn can_play(song) { Ocarina && song }
+
+fn has_bottle() {
+    Bottle
+    || BottleWithMilk
+    || BottleWithRedPotion
+    || BottleWithGreenPotion
+    || BottleWithBluePotion
+    || BottleWithFairy
+    || BottleWithFish
+    || BottleWithBlueFire
+    || BottleWithBugs
+    || BottleWithBigPoe
+    || BottleWithPoe
+}
+
+fn has_explosives() { BombBag }
+fn can_blast_or_smash() { has_explosives() || can_use(MegatonHammer) }
+
+fn can_cross_rainbow_bridge() {
+    setting(bridge) == Open
+    || setting(bridge) == Vanilla && ShadowMedallion && SpiritMedallion && LightArrows
+    || setting(bridge) == Stones && has_stones(setting(bridge_stones))
+    || setting(bridge) == Medallions && has_medallions(setting(bridge_medallions))
+    || setting(bridge) == Dungeons && has_dungeon_rewards(setting(bridge_rewards))
+    || setting(bridge) == Tokens && GoldSkulltulaToken >= setting(bridge_tokens)
+}
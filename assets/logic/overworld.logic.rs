@@ -1,13 +1,16 @@
 "Root" {
     time_of_day: None,
+    locations: {
+        "Links Pocket": true,
+    },
     exits: {
         // savewarp exits are hardcoded
-        "Temple of Time": true, //TODO can_play(PreludeOfLight)
-        "Sacred Forest Meadow": true, //TODO can_play(MinuetOfForest)
-        "Death Mountain Crater": true, //TODO can_play(BoleroOfFire) //TODO DMC point-to-point logic with health logic
-        "Lake Hylia": true, //TODO can_play(SerenadeOfWater)
-        "Graveyard Warp Pad Region": true, //TODO can_play(NocturneOfShadow)
-        "Desert Colossus": true, //TODO can_play(RequiemOfSpirit)
+        "Temple of Time": can_play(PreludeOfLight),
+        "Sacred Forest Meadow": can_play(MinuetOfForest),
+        "Death Mountain Crater": can_play(BoleroOfFire), //TODO DMC point-to-point logic
+        "Lake Hylia": can_play(SerenadeOfWater),
+        "Graveyard Warp Pad Region": can_play(NocturneOfShadow),
+        "Desert Colossus": can_play(RequiemOfSpirit),
     },
 }
 
@@ -81,12 +84,13 @@
         "Temple of Time Entrance": true,
         // We assume that if the player was able to bypass the Door of Time as the starting age, they can do so again as the non-starting age.
         // This is technically not safe since items are required to skip the DoT, but it's required to make pretty much any logic work, so the “know what you're doing if you use glitches” rule applies.
-        "Beyond Door of Time": true, //TODO check for non-starting age or can_play(SongOfTime)
+        "Beyond Door of Time": setting(open_door_of_time) || can_play(SongOfTime) || setting(starting_age) == Child && is_adult || setting(starting_age) == Adult && is_child,
     },
 }
 
 "Beyond Door of Time" {
     time_of_day: None,
+    time_travel: true,
     exits: {
         // We assume that if the player was able to bypass the Door of Time, they can do so again in reverse as both ages.
         "Temple of Time": true,
@@ -113,20 +117,24 @@
     time_of_day: OutsideGanonsCastle,
     exits: {
         "Castle Grounds": true,
-        "Inside Ganons Castle": at_dampe_time, //TODO require rainbow bridge
+        "Inside Ganons Castle": at_dampe_time && can_cross_rainbow_bridge(),
     },
 }
 
 "Kokiri Forest" {
     time_of_day: Static,
-    items: {
-        KokiriSword: is_child,
+    locations: {
+        "KF Kokiri Sword Chest": is_child,
+        "KF Midos Top Left Chest": true, //TODO separate region for Mido's house
+        "KF Midos Top Right Chest": true, //TODO separate region for Mido's house
+        "KF Midos Bottom Left Chest": true, //TODO separate region for Mido's house
+        "KF Midos Bottom Right Chest": true, //TODO separate region for Mido's house
     },
     exits: {
         "KF Links House": true,
         "Deku Tree": is_child && KokiriSword, //TODO require Deku Shield
         "Lost Woods": true,
-        "Lost Woods Bridge": true, //TODO require Deku Tree Clear or adult
+        "Lost Woods Bridge": is_adult || setting(open_forest) == Open || "Deku Tree Clear",
     },
 }
 
@@ -158,6 +166,8 @@
 
 "Sacred Forest Meadow" {
     time_of_day: Static,
+    // fairy fountain grotto
+    refill: Fairies,
     exits: {
         "Lost Woods": true,
         "Forest Temple": is_adult, //TODO require hookshot, separate region
@@ -170,25 +180,29 @@
         "Kakariko Village": true, //TODO gate behavior/trick, separate exit for the owl
         "Dodongos Cavern": true, //TODO item requirements for child access
         "Goron City": true,
-        "Death Mountain Crater": true, //TODO DMC point-to-point logic with health logic
+        "Death Mountain Crater": true, //TODO DMC point-to-point logic
     },
 }
 
 "Goron City" {
     time_of_day: None,
+    // recovery hearts from the pots
+    refill: Hearts,
     exits: {
         "Death Mountain Trail": true,
-        "Death Mountain Crater": is_adult, //TODO separate region for Darunia's chamber, DMC point-to-point logic with health logic
+        "Death Mountain Crater": is_adult, //TODO separate region for Darunia's chamber, DMC point-to-point logic
         "Lost Woods": true, //TODO item requirements
     },
 }
 
 "Death Mountain Crater" {
     time_of_day: Static,
-    exits: { //TODO DMC point-to-point logic with health logic
-        "Goron City": true,
-        "Death Mountain Trail": true,
-        "Fire Temple": is_adult,
+    exits: { //TODO DMC point-to-point logic
+        // Crossing the crater deals damage from falling rocks.
+        "Goron City": { access: true, damage: 1 },
+        "Death Mountain Trail": { access: true, damage: 1 },
+        // The heat in the lower crater and the Fire Temple is only survivable with the Goron Tunic.
+        "Fire Temple": { access: can_use(GoronTunic), damage: 1 }, //TODO fewer tunic requirements trick
     },
 }
 
@@ -249,7 +263,7 @@
     time_of_day: Static,
     exits: {
         "Graveyard": true,
-        "Shadow Temple": true, //TODO item/trick requirements
+        "Shadow Temple": can_use(DinsFire) || trick(shadow_fire_arrow_entry) && can_use(FireArrows),
     },
 }
 
@@ -284,7 +298,7 @@
     time_of_day: Static,
     exits: {
         "Gerudo Fortress": is_adult, //TODO separate wasteland regions
-        "Desert Colossus": is_adult, //TODO separate wasteland regions, item/trick requirements
+        "Desert Colossus": is_adult && (can_use(LensOfTruth) || trick(lens_wasteland)), //TODO separate wasteland regions, item requirements
     },
 }
 
@@ -0,0 +1,824 @@
+//! Parsing and lowering of the logic files in `assets/logic`, shared by `riirando_macros::regions!` and the runtime logic interpreter.
+
+use {
+    std::{
+        collections::{
+            BTreeMap,
+            HashMap,
+            HashSet,
+        },
+        fs,
+        io,
+        ops::Range,
+        path::{
+            Path,
+            PathBuf,
+        },
+    },
+    convert_case::{
+        Case,
+        Casing as _,
+    },
+    enum_iterator::all,
+    proc_macro2::{
+        Span,
+        TokenStream,
+    },
+    quote::{
+        ToTokens,
+        quote,
+    },
+    syn::{
+        *,
+        parse::{
+            Parse,
+            ParseStream,
+        },
+        visit::Visit,
+    },
+    crate::{
+        *,
+        Item,
+        rule::{
+            DUNGEON_REWARDS,
+            MEDALLIONS,
+            STONES,
+        },
+    },
+};
+
+/// A complete set of logic files with helpers expanded and all names resolved.
+pub struct LogicSet {
+    pub regions: BTreeMap<String, Region>,
+}
+
+/// A region as defined by a logic set.
+pub struct Region {
+    pub savewarp: Savewarp,
+    pub time_of_day: TimeOfDayBehavior,
+    /// Whether entering this region lets the player switch between child and adult, like the Master Sword pedestal.
+    pub time_travel: bool,
+    pub refill: Option<Refill>,
+    /// Whether Farore's Wind can be used in this region, i.e. to place a warp point or to return to one. Defaults to whether the region's savewarp is a dungeon.
+    pub farores_wind: bool,
+    pub locations: BTreeMap<Location, Rule>,
+    pub events: BTreeMap<String, Rule>,
+    pub exits: BTreeMap<String, Rule>,
+    /// The damage in hearts taken when using an exit, for exits which declare it.
+    pub damage: BTreeMap<String, u8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoadError {
+    #[error(transparent)] Io(#[from] io::Error),
+    #[error(transparent)] Syn(#[from] Error),
+    /// Errors in the logic files, formatted with their file path, line, column, and a snippet of the source.
+    #[error("{}", .0.join("\n"))]
+    Logic(Vec<String>),
+}
+
+impl LogicSet {
+    /// Loads `helpers.rs` and all `*.logic.rs` files from the given directory.
+    ///
+    /// Logic files in the `dungeons` subdirectory are named after a dungeon, e.g. `deku_tree.logic.rs`, and their regions use that dungeon's savewarp.
+    pub fn load(dir: &Path) -> std::result::Result<Self, LoadError> {
+        let mut sources = Vec::default();
+        let (helpers_source, helpers) = SourceFile::read(dir.join("helpers.rs"))?;
+        sources.push(helpers_source);
+        let helpers = parse2::<Helpers>(helpers).map_err(|e| locate(&sources, e))?;
+        let mut logic_files = Vec::default();
+        for (dir, is_dungeon) in [(dir.to_owned(), false), (dir.join("dungeons"), true)] {
+            for res in fs::read_dir(dir)? {
+                let path = res?.path();
+                let Some(stem) = path.file_name().and_then(|file_name| file_name.to_str()).and_then(|file_name| file_name.strip_suffix(".logic.rs")) else { continue };
+                let (source, tokens) = SourceFile::read(path.clone())?;
+                let span = source.span;
+                sources.push(source);
+                let mut logic_file = parse2::<LogicFile>(tokens).map_err(|e| locate(&sources, e))?;
+                if is_dungeon {
+                    logic_file.kind = FileKind::dungeon(stem).map_err(|msg| locate(&sources, Error::new(span, msg)))?;
+                }
+                logic_files.push(logic_file);
+            }
+        }
+        Self::lower(&helpers, logic_files).map_err(|e| locate(&sources, e))
+    }
+
+    fn lower(helpers: &Helpers, logic_files: Vec<LogicFile>) -> Result<Self> {
+        let mut region_infos = HashMap::<String, (FileKind, RegionInfo)>::default();
+        for LogicFile { kind, regions: file_regions } in logic_files {
+            for (name, info) in file_regions {
+                let span = info.span;
+                if region_infos.insert(name.clone(), (kind, info)).is_some() {
+                    return Err(Error::new(span, format!("region {name:?} is defined in multiple logic files")))
+                }
+            }
+        }
+        validate_regions(helpers, &region_infos)?;
+        let savewarp_regions = all::<Savewarp>()
+            .filter_map(|savewarp| Some((savewarp_region(savewarp, region_infos.keys())?.clone(), savewarp)))
+            .collect::<Vec<_>>();
+        let mut location_regions = HashMap::<Location, &String>::default();
+        let mut regions = BTreeMap::default();
+        for (name, (kind, RegionInfo { span: _, savewarp, time_of_day, time_travel, refill, farores_wind, locations, events, exits })) in &region_infos {
+            let savewarp = match (savewarp, kind.savewarp()) {
+                (Some(savewarp), inherited) => {
+                    let explicit = parse2::<Savewarp>(savewarp.to_token_stream())?;
+                    if let Some(inherited) = inherited.filter(|&inherited| inherited != explicit) {
+                        return Err(Error::new(savewarp.span(), format!("region {name:?} inherits the {inherited} savewarp from its logic file but has a conflicting savewarp to {:?}", savewarp.value())))
+                    }
+                    explicit
+                }
+                (None, Some(inherited)) => inherited,
+                (None, None) => Savewarp::Overworld,
+            };
+            let mut location_rules = BTreeMap::default();
+            for (location, access) in locations {
+                if let Some(other_region) = location_regions.insert(*location, name) {
+                    return Err(Error::new_spanned(&access.0, format!("location {:?} is defined in both {other_region:?} and {name:?}", location.name())))
+                }
+                location_rules.insert(*location, access.lower(helpers)?);
+            }
+            let events = events.iter()
+                .map(|(event, access)| Ok((event.clone(), access.lower(helpers)?)))
+                .collect::<Result<_>>()?;
+            let mut exit_rules = BTreeMap::default();
+            let mut damage = BTreeMap::default();
+            for (target, Exit { access, damage: exit_damage, .. }) in exits {
+                if let Some(exit_damage) = exit_damage {
+                    damage.insert(target.clone(), *exit_damage);
+                }
+                exit_rules.insert(target.clone(), access.lower(helpers)?);
+            }
+            if name == "Root" {
+                // savewarp exits are hardcoded
+                for (target, savewarp) in &savewarp_regions {
+                    exit_rules.insert(target.clone(), Rule::Savewarp(*savewarp));
+                }
+            }
+            regions.insert(name.clone(), Region { savewarp, time_of_day: *time_of_day, time_travel: *time_travel, refill: *refill, farores_wind: farores_wind.unwrap_or_else(|| savewarp.is_dungeon()), locations: location_rules, events, exits: exit_rules, damage });
+        }
+        Ok(Self { regions })
+    }
+}
+
+/// A parsed logic source file, kept around to report errors with their location.
+struct SourceFile {
+    path: PathBuf,
+    text: String,
+    /// The span of any token in the file, used to check which file an error's span belongs to.
+    span: Span,
+}
+
+impl SourceFile {
+    fn read(path: PathBuf) -> std::result::Result<(Self, TokenStream), LoadError> {
+        let text = fs::read_to_string(&path)?;
+        let tokens = text.parse::<TokenStream>().map_err(Error::from)?;
+        let span = tokens.clone().into_iter().next().map_or_else(Span::call_site, |token| token.span());
+        Ok((Self { path, text, span }, tokens))
+    }
+
+    /// Formats an error message with the file path, line, and column of the span, as well as a snippet of the source.
+    ///
+    /// The location is computed from the span's byte offsets into the file's text.
+    fn format_error(&self, span: Span, message: &str) -> String {
+        let Range { start, end } = span.byte_range();
+        let line_start = self.text[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.text[start..].find('\n').map_or(self.text.len(), |idx| start + idx);
+        let line_number = self.text[..start].matches('\n').count() + 1;
+        let column = self.text[line_start..start].chars().count();
+        let line = self.text[line_start..line_end].trim_end_matches('\r');
+        let width = self.text[start..end.clamp(start, line_end)].chars().count().max(1);
+        let gutter = " ".repeat(line_number.to_string().len());
+        format!(
+            "{message}\n{gutter}--> {}:{line_number}:{}\n{gutter} |\n{line_number} | {line}\n{gutter} | {}{}",
+            self.path.display(), column + 1,
+            " ".repeat(column), "^".repeat(width),
+        )
+    }
+}
+
+/// Attaches source locations to the messages of a syn error whose spans point into the given logic files.
+///
+/// Spans only carry locations when using proc-macro2's fallback implementation, so proc macros calling this should force it.
+fn locate(sources: &[SourceFile], e: Error) -> LoadError {
+    LoadError::Logic(e.into_iter().map(|e| {
+        let span = e.span();
+        let message = e.to_string();
+        match sources.iter().find(|source| source.span.join(span).is_some()) {
+            Some(source) => source.format_error(span, &message),
+            None => message,
+        }
+    }).collect())
+}
+
+/// Suggests the closest name to a misspelled one, e.g. of a region or event.
+fn did_you_mean<'a>(name: &str, candidates: impl IntoIterator<Item = &'a String>) -> String {
+    candidates.into_iter()
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|&(similarity, _)| similarity >= 0.8)
+        .max_by(|(similarity1, _), (similarity2, _)| similarity1.total_cmp(similarity2))
+        .map(|(_, candidate)| format!(", did you mean {candidate:?}?"))
+        .unwrap_or_default()
+}
+
+/// Currently we assume that the name of the savewarp is equal to the name of its target region.
+pub fn savewarp_region<'a>(savewarp: Savewarp, regions: impl IntoIterator<Item = &'a String>) -> Option<&'a String> {
+    regions.into_iter().find(|name| name.to_case(Case::Pascal) == savewarp.to_string())
+}
+
+/// Checks that exits and savewarps lead to declared regions and that every region other than Root can be entered.
+fn validate_regions(helpers: &Helpers, regions: &HashMap<String, (FileKind, RegionInfo)>) -> Result<()> {
+    let mut errors = Vec::default();
+    if !regions.contains_key("Root") {
+        errors.push(Error::new(Span::call_site(), "missing region \"Root\""));
+    }
+    let mut entered = HashSet::<&String>::default();
+    for savewarp in all::<Savewarp>() {
+        // Root has a hardcoded exit to each savewarp
+        if let Some(name) = savewarp_region(savewarp, regions.keys()) {
+            entered.insert(name);
+        } else {
+            errors.push(Error::new(Span::call_site(), format!("savewarp {savewarp} has no corresponding region")));
+        }
+    }
+    for (_, info) in regions.values() {
+        if let Some(savewarp) = &info.savewarp {
+            if !regions.contains_key(&savewarp.value()) {
+                errors.push(Error::new(savewarp.span(), format!("savewarp to unknown region {:?}{}", savewarp.value(), did_you_mean(&savewarp.value(), regions.keys()))));
+            } else if let Err(e) = parse2::<Savewarp>(savewarp.to_token_stream()) {
+                errors.push(e);
+            }
+        }
+        for exit in info.exits.values() {
+            if let Some((name, _)) = regions.get_key_value(&exit.name) {
+                entered.insert(name);
+            } else {
+                errors.push(Error::new(exit.span, format!("exit to unknown region {:?}{}", exit.name, did_you_mean(&exit.name, regions.keys()))));
+            }
+        }
+    }
+    for (name, (_, info)) in regions {
+        if name != "Root" && !entered.contains(name) {
+            errors.push(Error::new(info.span, format!("region {name:?} has no incoming exits")));
+        }
+    }
+    let events = regions.values().flat_map(|(_, info)| info.events.keys()).collect::<HashSet<_>>();
+    let mut event_refs = EventRefs::default();
+    for helper in helpers.0.values() {
+        event_refs.visit_expr(&helper.body);
+    }
+    for (_, info) in regions.values() {
+        for Access(expr) in info.locations.values().chain(info.events.values()).chain(info.exits.values().map(|exit| &exit.access)) {
+            event_refs.visit_expr(expr);
+        }
+    }
+    for event in event_refs.0 {
+        if !events.contains(&event.value()) {
+            errors.push(Error::new(event.span(), format!("unknown event {:?}{}", event.value(), did_you_mean(&event.value(), events.iter().copied()))));
+        }
+    }
+    errors.into_iter().reduce(|mut errors, e| { errors.combine(e); errors }).map_or(Ok(()), Err)
+}
+
+/// Collects the string literals in access expressions, which refer to events.
+#[derive(Default)]
+struct EventRefs(Vec<LitStr>);
+
+impl<'ast> Visit<'ast> for EventRefs {
+    fn visit_lit_str(&mut self, lit: &'ast LitStr) {
+        self.0.push(lit.clone());
+    }
+}
+
+/// Which part of the world a logic file describes, which determines the savewarp of its regions.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Overworld,
+    /// The dungeon with the given savewarp.
+    Dungeon(Savewarp),
+}
+
+impl FileKind {
+    /// Determines the kind of a logic file in the `dungeons` directory from its name without the `.logic.rs` extension.
+    fn dungeon(stem: &str) -> std::result::Result<Self, String> {
+        all::<Savewarp>()
+            .filter(|&savewarp| savewarp != Savewarp::Overworld)
+            .find(|savewarp| savewarp.to_string() == stem.to_case(Case::Pascal))
+            .map(Self::Dungeon)
+            .ok_or_else(|| format!("dungeon logic file {stem:?} is not named after a dungeon"))
+    }
+
+    /// The savewarp inherited by the regions in this file, if any.
+    fn savewarp(&self) -> Option<Savewarp> {
+        match self {
+            Self::Overworld => None,
+            Self::Dungeon(savewarp) => Some(*savewarp),
+        }
+    }
+}
+
+struct LogicFile {
+    /// Set when loading files from the `dungeons` directory.
+    kind: FileKind,
+    regions: HashMap<String, RegionInfo>,
+}
+
+impl Parse for LogicFile {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut regions = HashMap::default();
+        while !input.is_empty() {
+            let name = input.parse::<LitStr>()?;
+            if regions.insert(name.value(), RegionInfo::parse(&name, input)?).is_some() {
+                return Err(Error::new(name.span(), format!("logic file defines multiple regions named {:?}", name.value())))
+            }
+        }
+        Ok(Self { kind: FileKind::Overworld, regions })
+    }
+}
+
+struct RegionInfo {
+    /// The span of the region's name, used to report errors about the region as a whole.
+    span: Span,
+    /// The name of the region where this region's savewarp leads, validated once all regions are known.
+    savewarp: Option<LitStr>,
+    time_of_day: TimeOfDayBehavior,
+    time_travel: bool,
+    refill: Option<Refill>,
+    farores_wind: Option<bool>,
+    locations: HashMap<Location, Access>,
+    events: HashMap<String, Access>,
+    exits: HashMap<String, Exit>,
+}
+
+impl RegionInfo {
+    fn parse(name: &LitStr, input: ParseStream<'_>) -> Result<Self> {
+        let mut savewarp = None;
+        let mut time_of_day = None;
+        let mut time_travel = None;
+        let mut refill = None;
+        let mut farores_wind = None;
+        let mut locations = None;
+        let mut events = None;
+        let mut exits = None;
+        let content;
+        let brace = braced!(content in input);
+        let fields = content.parse_terminated(|input| Ok((input.span(), RegionInfoField::parse(input)?)), Token![,])?;
+        for (span, field) in fields {
+            match field {
+                RegionInfoField::Savewarp(new_savewarp) => if savewarp.replace(new_savewarp).is_some() { return Err(Error::new(span, "savewarp specified multiple times")) },
+                RegionInfoField::TimeOfDay(new_time_of_day) => if time_of_day.replace(new_time_of_day).is_some() { return Err(Error::new(span, "time_of_day specified multiple times")) },
+                RegionInfoField::TimeTravel(new_time_travel) => if time_travel.replace(new_time_travel).is_some() { return Err(Error::new(span, "time_travel specified multiple times")) },
+                RegionInfoField::Refill(new_refill) => if refill.replace(new_refill).is_some() { return Err(Error::new(span, "refill specified multiple times")) },
+                RegionInfoField::FaroresWind(new_farores_wind) => if farores_wind.replace(new_farores_wind).is_some() { return Err(Error::new(span, "farores_wind specified multiple times")) },
+                RegionInfoField::Locations(new_locations) => if locations.replace(new_locations).is_some() { return Err(Error::new(span, "locations specified multiple times")) },
+                RegionInfoField::Events(new_events) => if events.replace(new_events).is_some() { return Err(Error::new(span, "events specified multiple times")) },
+                RegionInfoField::Exits(new_exits) => if exits.replace(new_exits).is_some() { return Err(Error::new(span, "exits specified multiple times")) },
+            }
+        }
+        Ok(Self {
+            span: name.span(),
+            time_of_day: time_of_day.ok_or_else(|| Error::new(brace.span.join(), "missing time_of_day field in region info"))?,
+            time_travel: time_travel.unwrap_or_default(),
+            refill, farores_wind,
+            locations: locations.unwrap_or_default(),
+            events: events.unwrap_or_default(),
+            exits: exits.unwrap_or_default(),
+            savewarp,
+        })
+    }
+}
+
+enum RegionInfoField {
+    Savewarp(LitStr),
+    TimeOfDay(TimeOfDayBehavior),
+    TimeTravel(bool),
+    Refill(Refill),
+    FaroresWind(bool),
+    Locations(HashMap<Location, Access>),
+    Events(HashMap<String, Access>),
+    Exits(HashMap<String, Exit>),
+}
+
+impl Parse for RegionInfoField {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let field_name = input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        Ok(match &*field_name.to_string() {
+            "savewarp" => Self::Savewarp(input.parse()?),
+            "time_of_day" => Self::TimeOfDay(input.parse()?),
+            "time_travel" => Self::TimeTravel(input.parse::<LitBool>()?.value),
+            "refill" => Self::Refill(input.parse()?),
+            "farores_wind" => Self::FaroresWind(input.parse::<LitBool>()?.value),
+            "locations" => {
+                let mut locations_map = HashMap::default();
+                let content;
+                braced!(content in input);
+                let locations = content.parse_terminated(LocationAccess::parse, Token![,])?;
+                for LocationAccess { span, location, access } in locations {
+                    if locations_map.insert(location, access).is_some() {
+                        return Err(Error::new(span, format!("region defines location {:?} multiple times", location.name())))
+                    }
+                }
+                Self::Locations(locations_map)
+            }
+            "events" => {
+                let mut events_map = HashMap::default();
+                let content;
+                braced!(content in input);
+                let events = content.parse_terminated(Event::parse, Token![,])?;
+                for Event { span, name, access } in events {
+                    if events_map.insert(name.clone(), access).is_some() {
+                        return Err(Error::new(span, format!("region defines event {name:?} multiple times")))
+                    }
+                }
+                Self::Events(events_map)
+            }
+            "exits" => {
+                let mut exits_map = HashMap::default();
+                let content;
+                braced!(content in input);
+                let exits = content.parse_terminated(Exit::parse, Token![,])?;
+                for exit in exits {
+                    let span = exit.span;
+                    if let Some(Exit { name, .. }) = exits_map.insert(exit.name.clone(), exit) {
+                        return Err(Error::new(span, format!("region defines multiple exits to {name:?}")))
+                    }
+                }
+                Self::Exits(exits_map)
+            }
+            name => return Err(Error::new(field_name.span(), format!("unexpected region info field: {name}"))),
+        })
+    }
+}
+
+struct LocationAccess {
+    span: Span,
+    location: Location,
+    access: Access,
+}
+
+impl Parse for LocationAccess {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let span = input.span();
+        let location = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let access = input.parse()?;
+        Ok(Self { span, location, access })
+    }
+}
+
+struct Event {
+    span: Span,
+    name: String,
+    access: Access,
+}
+
+impl Parse for Event {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let name = input.parse::<LitStr>()?;
+        input.parse::<Token![:]>()?;
+        let access = input.parse()?;
+        Ok(Self { span: name.span(), name: name.value(), access })
+    }
+}
+
+/// An exit, written either as just an access expression or as a block like `{ access: is_adult, damage: 2 }` if using it deals damage.
+struct Exit {
+    /// The span of the target region's name.
+    span: Span,
+    name: String,
+    access: Access,
+    /// The damage in hearts taken when using the exit, if any.
+    damage: Option<u8>,
+}
+
+impl Parse for Exit {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let name = input.parse::<LitStr>()?;
+        input.parse::<Token![:]>()?;
+        if input.peek(token::Brace) {
+            let mut access = None;
+            let mut damage = None;
+            let content;
+            let brace = braced!(content in input);
+            let fields = content.parse_terminated(|input| Ok((input.span(), ExitField::parse(input)?)), Token![,])?;
+            for (span, field) in fields {
+                match field {
+                    ExitField::Access(new_access) => if access.replace(*new_access).is_some() { return Err(Error::new(span, "access specified multiple times")) },
+                    ExitField::Damage(new_damage) => if damage.replace(new_damage).is_some() { return Err(Error::new(span, "damage specified multiple times")) },
+                }
+            }
+            Ok(Self {
+                span: name.span(),
+                name: name.value(),
+                access: access.ok_or_else(|| Error::new(brace.span.join(), "missing access field in exit"))?,
+                damage,
+            })
+        } else {
+            let access = input.parse()?;
+            Ok(Self { span: name.span(), name: name.value(), access, damage: None })
+        }
+    }
+}
+
+enum ExitField {
+    Access(Box<Access>),
+    Damage(u8),
+}
+
+impl Parse for ExitField {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let field_name = input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        Ok(match &*field_name.to_string() {
+            "access" => Self::Access(Box::new(input.parse()?)),
+            "damage" => Self::Damage(input.parse::<LitInt>()?.base10_parse()?),
+            name => return Err(Error::new(field_name.span(), format!("unexpected exit field: {name}"))),
+        })
+    }
+}
+
+/// A set of helper functions that can be called from access expressions and are expanded when lowering them.
+#[derive(Default)]
+struct Helpers(HashMap<String, Helper>);
+
+impl Parse for Helpers {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut helpers = HashMap::default();
+        while !input.is_empty() {
+            input.parse::<Token![fn]>()?;
+            let name = input.parse::<Ident>()?;
+            let params;
+            parenthesized!(params in input);
+            let params = params.parse_terminated(Ident::parse, Token![,])?.into_iter().collect();
+            let body;
+            braced!(body in input);
+            let body = body.parse()?;
+            if helpers.insert(name.to_string(), Helper { params, body }).is_some() {
+                return Err(Error::new(name.span(), format!("multiple helpers named {name}")))
+            }
+        }
+        Ok(Self(helpers))
+    }
+}
+
+struct Helper {
+    params: Vec<Ident>,
+    body: Expr,
+}
+
+/// The context in which an access expression is lowered.
+#[derive(Default, Clone)]
+struct Scope {
+    /// Arguments of the helper currently being expanded, along with the scope they were passed from.
+    args: HashMap<String, (Expr, Scope)>,
+    /// Names of the helpers currently being expanded, used to detect recursion.
+    call_stack: Vec<String>,
+}
+
+impl Helpers {
+    fn enter(&self, name: &Ident, args: Vec<Expr>, scope: &Scope) -> Result<(&Helper, Scope)> {
+        let Some(helper) = self.0.get(&name.to_string()) else {
+            return Err(Error::new(name.span(), format!("unexpected function in access expression: {name}")))
+        };
+        if helper.params.len() != args.len() {
+            return Err(Error::new(name.span(), format!("helper {name} takes {} arguments but {} were given", helper.params.len(), args.len())))
+        }
+        if scope.call_stack.contains(&name.to_string()) {
+            return Err(Error::new(name.span(), format!("helper {name} is recursive")))
+        }
+        let mut call_stack = scope.call_stack.clone();
+        call_stack.push(name.to_string());
+        Ok((helper, Scope {
+            args: helper.params.iter().map(ToString::to_string).zip(args.into_iter().map(|arg| (arg, scope.clone()))).collect(),
+            call_stack,
+        }))
+    }
+
+    /// Resolves an expression which should name an item, optionally with a count, through argument bindings and alias helpers like `Hookshot`.
+    fn resolve_item(&self, expr: Expr, scope: &Scope) -> Result<(Item, u8)> {
+        match expr {
+            Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => if let Some(ident) = path.get_ident() {
+                if let Some((arg, arg_scope)) = scope.args.get(&ident.to_string()) {
+                    self.resolve_item(arg.clone(), arg_scope)
+                } else if let Ok(item) = parse2::<Item>(quote!(#ident)) {
+                    Ok((item, 1))
+                } else {
+                    let (helper, helper_scope) = self.enter(ident, Vec::default(), scope)?;
+                    self.resolve_item(helper.body.clone(), &helper_scope)
+                }
+            } else {
+                Err(Error::new_spanned(path, "unexpected path in access expression"))
+            },
+            Expr::Call(ExprCall { attrs, func, args, .. }) if attrs.is_empty() => {
+                let Expr::Path(ExprPath { attrs, qself: None, path }) = *func else {
+                    return Err(Error::new_spanned(func, "unexpected function in access expression"))
+                };
+                match path.get_ident().filter(|_| attrs.is_empty()).map(|ident| parse2::<Item>(quote!(#ident))) {
+                    Some(Ok(item)) => Ok((item, self.resolve_count(single_arg(&path, args)?, scope)?)),
+                    _ => Err(Error::new_spanned(path, "expected item")),
+                }
+            }
+            expr => Err(Error::new_spanned(expr, "expected item")),
+        }
+    }
+
+    /// Resolves an expression which should be an integer literal, e.g. an item count.
+    fn resolve_count(&self, expr: Expr, scope: &Scope) -> Result<u8> {
+        match expr {
+            Expr::Lit(ExprLit { attrs, lit: Lit::Int(count) }) if attrs.is_empty() => count.base10_parse(),
+            Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() && path.get_ident().is_some_and(|ident| scope.args.contains_key(&ident.to_string())) => {
+                let (arg, arg_scope) = &scope.args[&path.get_ident().unwrap().to_string()];
+                self.resolve_count(arg.clone(), arg_scope)
+            }
+            expr => Err(Error::new_spanned(expr, "expected an integer literal")),
+        }
+    }
+
+    /// Lowers an expression which should evaluate to a number, i.e. an integer literal or a reference to a numeric setting.
+    fn lower_count(&self, expr: Expr, scope: &Scope) -> Result<Count> {
+        Ok(match self.resolve_setting(&expr, scope)? {
+            Some((name, SettingKind::Number)) => Count::Setting(name.to_string()),
+            Some((name, _)) => return Err(Error::new_spanned(expr, format!("setting {name} is not a number"))),
+            None => Count::Literal(self.resolve_count(expr, scope)?),
+        })
+    }
+
+    /// If the expression is a setting reference like `setting(open_forest)`, returns the setting's name and type.
+    fn resolve_setting(&self, expr: &Expr, scope: &Scope) -> Result<Option<(Ident, SettingKind)>> {
+        match expr {
+            Expr::Call(ExprCall { attrs, func, args, .. }) if attrs.is_empty() && matches!(&**func, Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() && path.is_ident("setting")) => {
+                let name = match single_arg(func, args.clone())? {
+                    Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => path.get_ident().cloned(),
+                    _ => None,
+                };
+                let Some(name) = name else {
+                    return Err(Error::new_spanned(args, "setting takes the name of a setting as its argument"))
+                };
+                let Some(kind) = Settings::kind(&name.to_string()) else {
+                    return Err(Error::new(name.span(), format!("unknown setting: {name}")))
+                };
+                Ok(Some((name, kind)))
+            }
+            Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => if let Some((arg, arg_scope)) = path.get_ident().and_then(|ident| scope.args.get(&ident.to_string())) {
+                self.resolve_setting(arg, arg_scope)
+            } else {
+                Ok(None)
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn lower(&self, expr: Expr, scope: &Scope) -> Result<Rule> {
+        if let Some((name, kind)) = self.resolve_setting(&expr, scope)? {
+            return if let SettingKind::Bool = kind {
+                Ok(Rule::BoolSetting(name.to_string()))
+            } else {
+                Err(Error::new_spanned(expr, format!("setting {name} is not a boolean, compare it to a value instead")))
+            }
+        }
+        Ok(match expr {
+            Expr::Binary(ExprBinary { attrs, left, op: BinOp::And(_), right }) if attrs.is_empty() => Rule::and(self.lower(*left, scope)?, self.lower(*right, scope)?),
+            Expr::Binary(ExprBinary { attrs, left, op: BinOp::Or(_), right }) if attrs.is_empty() => Rule::or(self.lower(*left, scope)?, self.lower(*right, scope)?),
+            Expr::Binary(ExprBinary { attrs, left, op: bin_op @ (BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_)), right }) if attrs.is_empty() => {
+                let op = match bin_op {
+                    BinOp::Eq(_) => CmpOp::Eq,
+                    BinOp::Ne(_) => CmpOp::Ne,
+                    BinOp::Lt(_) => CmpOp::Lt,
+                    BinOp::Le(_) => CmpOp::Le,
+                    BinOp::Gt(_) => CmpOp::Gt,
+                    _ => CmpOp::Ge,
+                };
+                if let Some((name, kind)) = self.resolve_setting(&left, scope)? {
+                    // setting comparison, e.g. `setting(open_forest) == Open` or `setting(bridge_tokens) > 0`
+                    return match kind {
+                        SettingKind::Bool => Err(Error::new_spanned(bin_op, format!("setting {name} is a boolean, use it without comparison"))),
+                        SettingKind::Number => Ok(Rule::NumberSetting(name.to_string(), op, self.lower_count(*right, scope)?)),
+                        SettingKind::Enum { type_name, variants } => {
+                            if !matches!(op, CmpOp::Eq | CmpOp::Ne) {
+                                return Err(Error::new_spanned(bin_op, format!("setting {name} can only be compared using == or !=")))
+                            }
+                            let variant = match &*right {
+                                Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => path.get_ident().filter(|variant| variants.contains(&&*variant.to_string())).cloned(),
+                                _ => None,
+                            };
+                            let Some(variant) = variant else {
+                                return Err(Error::new_spanned(right, format!("setting {name} must be compared to one of {}", variants.join(", "))))
+                            };
+                            Ok(Rule::EnumSetting { name: name.to_string(), type_name, op, variant: variant.to_string() })
+                        }
+                    }
+                }
+                // item count comparison, e.g. `GoldSkulltulaToken >= 50`
+                let item = match &*left {
+                    Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => path.get_ident().and_then(|ident| parse2::<Item>(quote!(#ident)).ok()),
+                    _ => None,
+                };
+                let Some(item) = item else {
+                    return Err(Error::new_spanned(left, "left-hand side of comparison must be an item"))
+                };
+                Rule::Item(item, op, self.lower_count(*right, scope)?)
+            }
+            Expr::Paren(ExprParen { attrs, expr, .. }) if attrs.is_empty() => self.lower(*expr, scope)?,
+            Expr::Unary(ExprUnary { attrs, op: UnOp::Not(_), expr }) if attrs.is_empty() => !self.lower(*expr, scope)?,
+            Expr::Call(ExprCall { attrs, func, args, .. }) if attrs.is_empty() => {
+                let Expr::Path(ExprPath { attrs, qself: None, path }) = *func else {
+                    return Err(Error::new_spanned(func, "unexpected function in access expression"))
+                };
+                let Some(ident) = path.get_ident().filter(|_| attrs.is_empty()) else {
+                    return Err(Error::new_spanned(path, "unexpected path in access expression"))
+                };
+                let args = args.into_iter().collect::<Vec<_>>();
+                if let Ok(item) = parse2::<Item>(quote!(#ident)) {
+                    // item count, e.g. `SmallKeyForestTemple(5)` or `ProgressiveHookshot(2)`
+                    Rule::Item(item, CmpOp::Ge, self.lower_count(single_arg(ident, args)?, scope)?)
+                } else {
+                    match &*ident.to_string() {
+                        "can_use" => {
+                            let (item, count) = self.resolve_item(single_arg(ident, args)?, scope)?;
+                            let has_item = Rule::has(item, count);
+                            match item {
+                                Item::Bow | Item::MegatonHammer | Item::IronBoots | Item::HoverBoots | Item::ProgressiveHookshot | Item::GoronTunic | Item::ZoraTunic | Item::MirrorShield => Rule::and(Rule::IsAdult, has_item),
+                                Item::ProgressiveStrengthUpgrade if count >= 2 => Rule::and(Rule::IsAdult, has_item),
+                                Item::FireArrows | Item::IceArrows | Item::LightArrows => Rule::and(Rule::and(Rule::and(Rule::IsAdult, has_item), Rule::has(Item::Bow, 1)), Rule::has(Item::MagicMeter, 1)),
+                                Item::Slingshot | Item::Boomerang | Item::KokiriSword | Item::DekuShield => Rule::and(Rule::IsChild, has_item),
+                                Item::DinsFire | Item::FaroresWind | Item::NayrusLove | Item::LensOfTruth => Rule::and(has_item, Rule::has(Item::MagicMeter, 1)),
+                                _ => has_item,
+                            }
+                        }
+                        "trick" => {
+                            let name = match single_arg(ident, args)? {
+                                Expr::Path(ExprPath { attrs, qself: None, path }) if attrs.is_empty() => path.get_ident().cloned(),
+                                _ => None,
+                            };
+                            let Some(name) = name else {
+                                return Err(Error::new(ident.span(), "trick takes the ID of a trick as its argument"))
+                            };
+                            let Some(trick) = Trick::from_id(&name.to_string()) else {
+                                return Err(Error::new(name.span(), format!("unknown trick: {name}")))
+                            };
+                            Rule::Trick(trick)
+                        }
+                        "has_stones" => Rule::CountOf(STONES.to_vec(), self.lower_count(single_arg(ident, args)?, scope)?),
+                        "has_medallions" => Rule::CountOf(MEDALLIONS.to_vec(), self.lower_count(single_arg(ident, args)?, scope)?),
+                        "has_dungeon_rewards" => Rule::CountOf(DUNGEON_REWARDS.to_vec(), self.lower_count(single_arg(ident, args)?, scope)?),
+                        _ => {
+                            let (helper, helper_scope) = self.enter(ident, args, scope)?;
+                            self.lower(helper.body.clone(), &helper_scope)?
+                        }
+                    }
+                }
+            }
+            Expr::Lit(ExprLit { attrs, lit: Lit::Bool(LitBool { value, .. }) }) if attrs.is_empty() => Rule::Const(value),
+            Expr::Lit(ExprLit { attrs, lit: Lit::Str(event) }) if attrs.is_empty() => Rule::Event(event.value()),
+            Expr::Path(ExprPath { attrs, qself, path }) if attrs.is_empty() && qself.is_none() => if let Some(ident) = path.get_ident() {
+                match &*ident.to_string() {
+                    "at_dampe_time" => Rule::AtDampeTime,
+                    "at_day" => Rule::AtDay,
+                    "at_night" => Rule::AtNight,
+                    "is_adult" => Rule::IsAdult,
+                    "is_child" => Rule::IsChild,
+                    name => if let Some((arg, arg_scope)) = scope.args.get(name) {
+                        self.lower(arg.clone(), arg_scope)?
+                    } else if let Ok(item) = parse2::<Item>(quote!(#ident)) {
+                        Rule::has(item, 1)
+                    } else if self.0.contains_key(name) {
+                        // helpers without parameters, e.g. item aliases like `Hookshot`, can be used without parentheses
+                        let (helper, helper_scope) = self.enter(ident, Vec::default(), scope)?;
+                        self.lower(helper.body.clone(), &helper_scope)?
+                    } else {
+                        return Err(Error::new(ident.span(), format!("unexpected identifier in access expression: {ident}")))
+                    },
+                }
+            } else {
+                return Err(Error::new_spanned(path, "unexpected path in access expression"))
+            },
+            expr => return Err(Error::new_spanned(expr, "unexpected access expression")),
+        })
+    }
+}
+
+/// Returns the only argument of a function call, with errors pointing at the function.
+fn single_arg(func: impl ToTokens, args: impl IntoIterator<Item = Expr>) -> Result<Expr> {
+    let mut args = args.into_iter();
+    match (args.next(), args.next()) {
+        (Some(arg), None) => Ok(arg),
+        _ => Err(Error::new_spanned(func, "expected exactly 1 argument")),
+    }
+}
+
+/// An access expression as written in a logic file. Use [`Access::lower`] to resolve it to a [`Rule`].
+struct Access(Expr);
+
+impl Access {
+    fn lower(&self, helpers: &Helpers) -> Result<Rule> {
+        helpers.lower(self.0.clone(), &Scope::default())
+    }
+}
+
+impl Parse for Access {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        Ok(Self(input.parse()?))
+    }
+}
@@ -0,0 +1,41 @@
+use {
+    derive_more::Display,
+    enum_iterator::Sequence,
+    crate::Savewarp,
+};
+
+/// A dungeon which has a Master Quest variant.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
+pub enum Dungeon {
+    DekuTree,
+    DodongosCavern,
+    JabuJabusBelly,
+    ForestTemple,
+    FireTemple,
+    WaterTemple,
+    ShadowTemple,
+    SpiritTemple,
+    BottomOfTheWell,
+    IceCavern,
+    GerudoTrainingGround,
+    InsideGanonsCastle,
+}
+
+impl Dungeon {
+    pub fn savewarp(&self) -> Savewarp {
+        match self {
+            Self::DekuTree => Savewarp::DekuTree,
+            Self::DodongosCavern => Savewarp::DodongosCavern,
+            Self::JabuJabusBelly => Savewarp::JabuJabusBelly,
+            Self::ForestTemple => Savewarp::ForestTemple,
+            Self::FireTemple => Savewarp::FireTemple,
+            Self::WaterTemple => Savewarp::WaterTemple,
+            Self::ShadowTemple => Savewarp::ShadowTemple,
+            Self::SpiritTemple => Savewarp::SpiritTemple,
+            Self::BottomOfTheWell => Savewarp::BottomOfTheWell,
+            Self::IceCavern => Savewarp::IceCavern,
+            Self::GerudoTrainingGround => Savewarp::GerudoTrainingGround,
+            Self::InsideGanonsCastle => Savewarp::InsideGanonsCastle,
+        }
+    }
+}
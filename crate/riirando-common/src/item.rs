@@ -0,0 +1,258 @@
+use {
+    enum_iterator::Sequence,
+    proc_macro2::TokenStream,
+    quote::{
+        ToTokens,
+        quote,
+    },
+    syn::{
+        Ident,
+        parse::{
+            Parse,
+            ParseStream,
+        },
+    },
+};
+
+/// What kind of item this is, used to decide which shuffle settings and location restrictions apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+pub enum ItemKind {
+    Item,
+    Song,
+    DungeonReward,
+    Token,
+    SmallKey,
+    BossKey,
+    Map,
+    Compass,
+}
+
+/// How important an item is for beating the game. Corresponds to the `advancement` field of the Python randomizer's item table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+pub enum ItemClass {
+    /// Can be required by logic.
+    Progression,
+    /// Never required by logic, but not placed as filler either.
+    Useful,
+    /// Filler that can be used to pad the item pool.
+    Junk,
+}
+
+macro_rules! items {
+    ($($variant:ident: $name:literal, $kind:ident, $class:ident, $get_item_id:expr;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
+        pub enum Item {
+            $($variant,)*
+        }
+
+        impl Item {
+            /// The item's name as used in the Python randomizer's spoiler logs and plandos.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            pub fn kind(&self) -> ItemKind {
+                match self {
+                    $(Self::$variant => ItemKind::$kind,)*
+                }
+            }
+
+            pub fn class(&self) -> ItemClass {
+                match self {
+                    $(Self::$variant => ItemClass::$class,)*
+                }
+            }
+
+            /// The index of this item's entry in the get-item table, or `None` for items that are given by cutscenes in vanilla.
+            pub fn get_item_id(&self) -> Option<u16> {
+                match self {
+                    $(Self::$variant => $get_item_id,)*
+                }
+            }
+        }
+
+        impl Parse for Item {
+            fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+                let ident = input.parse::<Ident>()?;
+                Ok(match &*ident.to_string() {
+                    $(stringify!($variant) => Self::$variant,)*
+                    name => return Err(syn::Error::new(ident.span(), format!("expected item, found ident {name}"))),
+                })
+            }
+        }
+
+        impl ToTokens for Item {
+            fn to_tokens(&self, tokens: &mut TokenStream) {
+                let stream = match self {
+                    $(Self::$variant => quote!(Item::$variant),)*
+                };
+                stream.to_tokens(tokens);
+            }
+        }
+    };
+}
+
+items! {
+    // equipment
+    KokiriSword: "Kokiri Sword", Item, Progression, Some(0x27);
+    GiantsKnife: "Giants Knife", Item, Useful, Some(0x28);
+    BiggoronSword: "Biggoron Sword", Item, Progression, Some(0x57);
+    DekuShield: "Deku Shield", Item, Progression, Some(0x29);
+    HylianShield: "Hylian Shield", Item, Progression, Some(0x2a);
+    MirrorShield: "Mirror Shield", Item, Progression, Some(0x2b);
+    GoronTunic: "Goron Tunic", Item, Progression, Some(0x2c);
+    ZoraTunic: "Zora Tunic", Item, Progression, Some(0x2d);
+    IronBoots: "Iron Boots", Item, Progression, Some(0x2e);
+    HoverBoots: "Hover Boots", Item, Progression, Some(0x2f);
+    // progressive upgrades
+    ProgressiveHookshot: "Progressive Hookshot", Item, Progression, Some(0x80);
+    ProgressiveStrengthUpgrade: "Progressive Strength Upgrade", Item, Progression, Some(0x81);
+    BombBag: "Bomb Bag", Item, Progression, Some(0x82);
+    Bow: "Bow", Item, Progression, Some(0x83);
+    Slingshot: "Slingshot", Item, Progression, Some(0x84);
+    ProgressiveWallet: "Progressive Wallet", Item, Progression, Some(0x85);
+    ProgressiveScale: "Progressive Scale", Item, Progression, Some(0x86);
+    DekuNutCapacity: "Deku Nut Capacity", Item, Useful, Some(0x87);
+    DekuStickCapacity: "Deku Stick Capacity", Item, Useful, Some(0x88);
+    MagicMeter: "Magic Meter", Item, Progression, Some(0x8a);
+    Ocarina: "Ocarina", Item, Progression, Some(0x8b);
+    // inventory items
+    Boomerang: "Boomerang", Item, Progression, Some(0x06);
+    LensOfTruth: "Lens of Truth", Item, Progression, Some(0x0a);
+    MegatonHammer: "Megaton Hammer", Item, Progression, Some(0x0d);
+    MagicBean: "Magic Bean", Item, Progression, Some(0x16);
+    FireArrows: "Fire Arrows", Item, Progression, Some(0x58);
+    IceArrows: "Ice Arrows", Item, Useful, Some(0x59);
+    LightArrows: "Light Arrows", Item, Progression, Some(0x5a);
+    DinsFire: "Dins Fire", Item, Progression, Some(0x5c);
+    FaroresWind: "Farores Wind", Item, Progression, Some(0x5d);
+    NayrusLove: "Nayrus Love", Item, Useful, Some(0x5e);
+    // bottles
+    Bottle: "Bottle", Item, Progression, Some(0x0f);
+    BottleWithMilk: "Bottle with Milk", Item, Progression, Some(0x14);
+    RutosLetter: "Rutos Letter", Item, Progression, Some(0x15);
+    BottleWithRedPotion: "Bottle with Red Potion", Item, Progression, Some(0x8c);
+    BottleWithGreenPotion: "Bottle with Green Potion", Item, Progression, Some(0x8d);
+    BottleWithBluePotion: "Bottle with Blue Potion", Item, Progression, Some(0x8e);
+    BottleWithFairy: "Bottle with Fairy", Item, Progression, Some(0x8f);
+    BottleWithFish: "Bottle with Fish", Item, Progression, Some(0x90);
+    BottleWithBlueFire: "Bottle with Blue Fire", Item, Progression, Some(0x91);
+    BottleWithBugs: "Bottle with Bugs", Item, Progression, Some(0x92);
+    BottleWithBigPoe: "Bottle with Big Poe", Item, Progression, Some(0x93);
+    BottleWithPoe: "Bottle with Poe", Item, Progression, Some(0x94);
+    // child trade sequence
+    WeirdEgg: "Weird Egg", Item, Progression, Some(0x47);
+    ZeldasLetter: "Zeldas Letter", Item, Progression, Some(0x0b);
+    KeatonMask: "Keaton Mask", Item, Useful, Some(0x1a);
+    SkullMask: "Skull Mask", Item, Useful, Some(0x17);
+    SpookyMask: "Spooky Mask", Item, Useful, Some(0x18);
+    BunnyHood: "Bunny Hood", Item, Useful, Some(0x1b);
+    GoronMask: "Goron Mask", Item, Useful, Some(0x51);
+    ZoraMask: "Zora Mask", Item, Useful, Some(0x52);
+    GerudoMask: "Gerudo Mask", Item, Useful, Some(0x53);
+    MaskOfTruth: "Mask of Truth", Item, Useful, Some(0x1c);
+    // adult trade sequence
+    PocketEgg: "Pocket Egg", Item, Progression, Some(0x1d);
+    PocketCucco: "Pocket Cucco", Item, Progression, Some(0x1e);
+    Cojiro: "Cojiro", Item, Progression, Some(0x0e);
+    OddMushroom: "Odd Mushroom", Item, Progression, Some(0x1f);
+    OddPotion: "Odd Potion", Item, Progression, Some(0x20);
+    PoachersSaw: "Poachers Saw", Item, Progression, Some(0x21);
+    BrokenSword: "Broken Sword", Item, Progression, Some(0x22);
+    Prescription: "Prescription", Item, Progression, Some(0x23);
+    EyeballFrog: "Eyeball Frog", Item, Progression, Some(0x24);
+    Eyedrops: "Eyedrops", Item, Progression, Some(0x25);
+    ClaimCheck: "Claim Check", Item, Progression, Some(0x26);
+    // quest status
+    StoneOfAgony: "Stone of Agony", Item, Progression, Some(0x39);
+    GerudoMembershipCard: "Gerudo Membership Card", Item, Progression, Some(0x3a);
+    HeartContainer: "Heart Container", Item, Progression, Some(0x3d);
+    PieceOfHeart: "Piece of Heart", Item, Progression, Some(0x3e);
+    DoubleDefense: "Double Defense", Item, Useful, Some(0xb8);
+    GoldSkulltulaToken: "Gold Skulltula Token", Token, Progression, Some(0x5b);
+    // songs
+    ZeldasLullaby: "Zeldas Lullaby", Song, Progression, Some(0xc1);
+    EponasSong: "Eponas Song", Song, Progression, Some(0xc2);
+    SariasSong: "Sarias Song", Song, Progression, Some(0xc3);
+    SunsSong: "Suns Song", Song, Progression, Some(0xc4);
+    SongOfTime: "Song of Time", Song, Progression, Some(0xc5);
+    SongOfStorms: "Song of Storms", Song, Progression, Some(0xc6);
+    MinuetOfForest: "Minuet of Forest", Song, Progression, Some(0xbb);
+    BoleroOfFire: "Bolero of Fire", Song, Progression, Some(0xbc);
+    SerenadeOfWater: "Serenade of Water", Song, Progression, Some(0xbd);
+    RequiemOfSpirit: "Requiem of Spirit", Song, Progression, Some(0xbe);
+    NocturneOfShadow: "Nocturne of Shadow", Song, Progression, Some(0xbf);
+    PreludeOfLight: "Prelude of Light", Song, Progression, Some(0xc0);
+    // dungeon rewards
+    KokiriEmerald: "Kokiri Emerald", DungeonReward, Progression, None;
+    GoronRuby: "Goron Ruby", DungeonReward, Progression, None;
+    ZoraSapphire: "Zora Sapphire", DungeonReward, Progression, None;
+    ForestMedallion: "Forest Medallion", DungeonReward, Progression, None;
+    FireMedallion: "Fire Medallion", DungeonReward, Progression, None;
+    WaterMedallion: "Water Medallion", DungeonReward, Progression, None;
+    SpiritMedallion: "Spirit Medallion", DungeonReward, Progression, None;
+    ShadowMedallion: "Shadow Medallion", DungeonReward, Progression, None;
+    LightMedallion: "Light Medallion", DungeonReward, Progression, None;
+    // small keys
+    SmallKeyForestTemple: "Small Key (Forest Temple)", SmallKey, Progression, Some(0xaf);
+    SmallKeyFireTemple: "Small Key (Fire Temple)", SmallKey, Progression, Some(0xb0);
+    SmallKeyWaterTemple: "Small Key (Water Temple)", SmallKey, Progression, Some(0xb1);
+    SmallKeySpiritTemple: "Small Key (Spirit Temple)", SmallKey, Progression, Some(0xb2);
+    SmallKeyShadowTemple: "Small Key (Shadow Temple)", SmallKey, Progression, Some(0xb3);
+    SmallKeyBottomOfTheWell: "Small Key (Bottom of the Well)", SmallKey, Progression, Some(0xb4);
+    SmallKeyGerudoTrainingGround: "Small Key (Gerudo Training Ground)", SmallKey, Progression, Some(0xb5);
+    SmallKeyThievesHideout: "Small Key (Thieves Hideout)", SmallKey, Progression, Some(0xb6);
+    SmallKeyGanonsCastle: "Small Key (Ganons Castle)", SmallKey, Progression, Some(0xb7);
+    // boss keys
+    BossKeyForestTemple: "Boss Key (Forest Temple)", BossKey, Progression, Some(0x95);
+    BossKeyFireTemple: "Boss Key (Fire Temple)", BossKey, Progression, Some(0x96);
+    BossKeyWaterTemple: "Boss Key (Water Temple)", BossKey, Progression, Some(0x97);
+    BossKeySpiritTemple: "Boss Key (Spirit Temple)", BossKey, Progression, Some(0x98);
+    BossKeyShadowTemple: "Boss Key (Shadow Temple)", BossKey, Progression, Some(0x99);
+    BossKeyGanonsCastle: "Boss Key (Ganons Castle)", BossKey, Progression, Some(0x9a);
+    // maps
+    MapDekuTree: "Map (Deku Tree)", Map, Useful, Some(0xa5);
+    MapDodongosCavern: "Map (Dodongos Cavern)", Map, Useful, Some(0xa6);
+    MapJabuJabusBelly: "Map (Jabu Jabus Belly)", Map, Useful, Some(0xa7);
+    MapForestTemple: "Map (Forest Temple)", Map, Useful, Some(0xa8);
+    MapFireTemple: "Map (Fire Temple)", Map, Useful, Some(0xa9);
+    MapWaterTemple: "Map (Water Temple)", Map, Useful, Some(0xaa);
+    MapSpiritTemple: "Map (Spirit Temple)", Map, Useful, Some(0xab);
+    MapShadowTemple: "Map (Shadow Temple)", Map, Useful, Some(0xac);
+    MapBottomOfTheWell: "Map (Bottom of the Well)", Map, Useful, Some(0xad);
+    MapIceCavern: "Map (Ice Cavern)", Map, Useful, Some(0xae);
+    // compasses
+    CompassDekuTree: "Compass (Deku Tree)", Compass, Useful, Some(0x9b);
+    CompassDodongosCavern: "Compass (Dodongos Cavern)", Compass, Useful, Some(0x9c);
+    CompassJabuJabusBelly: "Compass (Jabu Jabus Belly)", Compass, Useful, Some(0x9d);
+    CompassForestTemple: "Compass (Forest Temple)", Compass, Useful, Some(0x9e);
+    CompassFireTemple: "Compass (Fire Temple)", Compass, Useful, Some(0x9f);
+    CompassWaterTemple: "Compass (Water Temple)", Compass, Useful, Some(0xa0);
+    CompassSpiritTemple: "Compass (Spirit Temple)", Compass, Useful, Some(0xa1);
+    CompassShadowTemple: "Compass (Shadow Temple)", Compass, Useful, Some(0xa2);
+    CompassBottomOfTheWell: "Compass (Bottom of the Well)", Compass, Useful, Some(0xa3);
+    CompassIceCavern: "Compass (Ice Cavern)", Compass, Useful, Some(0xa4);
+    // ammo and other junk
+    Bombs5: "Bombs (5)", Item, Junk, Some(0x01);
+    Bombs10: "Bombs (10)", Item, Junk, Some(0x66);
+    Bombs20: "Bombs (20)", Item, Junk, Some(0x67);
+    Bombchus5: "Bombchus (5)", Item, Junk, Some(0x6a);
+    Bombchus10: "Bombchus (10)", Item, Junk, Some(0x03);
+    Bombchus20: "Bombchus (20)", Item, Junk, Some(0x6b);
+    DekuStick1: "Deku Stick (1)", Item, Junk, Some(0x07);
+    DekuNuts5: "Deku Nuts (5)", Item, Junk, Some(0x02);
+    DekuNuts10: "Deku Nuts (10)", Item, Junk, Some(0x64);
+    DekuSeeds30: "Deku Seeds (30)", Item, Junk, Some(0x69);
+    Arrows5: "Arrows (5)", Item, Junk, Some(0x49);
+    Arrows10: "Arrows (10)", Item, Junk, Some(0x4a);
+    Arrows30: "Arrows (30)", Item, Junk, Some(0x4b);
+    Rupee1: "Rupee (1)", Item, Junk, Some(0x4c);
+    Rupees5: "Rupees (5)", Item, Junk, Some(0x4d);
+    Rupees20: "Rupees (20)", Item, Junk, Some(0x4e);
+    Rupees50: "Rupees (50)", Item, Junk, Some(0x55);
+    Rupees200: "Rupees (200)", Item, Junk, Some(0x56);
+    RecoveryHeart: "Recovery Heart", Item, Junk, Some(0x48);
+    IceTrap: "Ice Trap", Item, Junk, Some(0x7c);
+}
@@ -8,7 +8,6 @@ use {
         Sequence,
         all,
     },
-    enumset::EnumSetType,
     proc_macro2::{
         Span,
         TokenStream,
@@ -26,8 +25,24 @@ use {
         },
     },
 };
+pub use crate::{
+    dungeon::*,
+    item::*,
+    location::*,
+    rule::*,
+    settings::*,
+    trick::*,
+};
+
+pub mod ast;
+mod dungeon;
+mod item;
+mod location;
+mod rule;
+mod settings;
+mod trick;
 
-#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
 pub enum Savewarp {
     Overworld,
     DekuTree,
@@ -59,15 +74,23 @@ pub enum Savewarp {
     ThievesHideout,
 }
 
+impl Savewarp {
+    /// Whether this is the savewarp of a dungeon, as opposed to an overworld area.
+    pub fn is_dungeon(&self) -> bool {
+        !matches!(self, Self::Overworld | Self::KfLinksHouse | Self::ThievesHideout)
+    }
+}
+
 impl Parse for Savewarp {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        let name = input.parse::<LitStr>()?.value().to_case(Case::Pascal);
+        let lit = input.parse::<LitStr>()?;
+        let name = lit.value().to_case(Case::Pascal);
         for variant in all::<Self>() {
             if name == variant.to_string() {
                 return Ok(variant)
             }
         }
-        Err(input.error(format!("expected savewarp, found string literal {name:?}")))
+        Err(syn::Error::new(lit.span(), format!("expected savewarp, found string literal {name:?}")))
     }
 }
 
@@ -78,6 +101,7 @@ impl ToTokens for Savewarp {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
 pub enum TimeOfDayBehavior {
     /// Cannot alter time of day in this region. Used for dungeons as well as helper regions like Root.
     None,
@@ -91,12 +115,13 @@ pub enum TimeOfDayBehavior {
 
 impl Parse for TimeOfDayBehavior {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        Ok(match &*input.parse::<Ident>()?.to_string() {
+        let ident = input.parse::<Ident>()?;
+        Ok(match &*ident.to_string() {
             "None" => Self::None,
             "Static" => Self::Static,
             "Passes" => Self::Passes,
             "OutsideGanonsCastle" => Self::OutsideGanonsCastle,
-            name => return Err(input.error(format!("expected time-of-day behavior, found ident {name}"))),
+            name => return Err(syn::Error::new(ident.span(), format!("expected time-of-day behavior, found ident {name}"))),
         })
     }
 }
@@ -113,24 +138,31 @@ impl ToTokens for TimeOfDayBehavior {
     }
 }
 
-#[derive(Debug, Hash, EnumSetType)]
-pub enum Item {
-    KokiriSword,
+/// A way to restore health which is always available in a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+pub enum Refill {
+    /// Recovery hearts, e.g. from grass or pots. Refills hearts to the maximum.
+    Hearts,
+    /// A fairy fountain. Refills hearts to the maximum and fills all empty bottles with fairies.
+    Fairies,
 }
 
-impl Parse for Item {
+impl Parse for Refill {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        Ok(match &*input.parse::<Ident>()?.to_string() {
-            "KokiriSword" => Self::KokiriSword,
-            name => return Err(input.error(format!("expected item, found ident {name}"))),
+        let ident = input.parse::<Ident>()?;
+        Ok(match &*ident.to_string() {
+            "Hearts" => Self::Hearts,
+            "Fairies" => Self::Fairies,
+            name => return Err(syn::Error::new(ident.span(), format!("expected refill, found ident {name}"))),
         })
     }
 }
 
-impl ToTokens for Item {
+impl ToTokens for Refill {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let stream = match self {
-            Self::KokiriSword => quote!(Item::KokiriSword),
+            Self::Hearts => quote!(Refill::Hearts),
+            Self::Fairies => quote!(Refill::Fairies),
         };
         stream.to_tokens(tokens);
     }
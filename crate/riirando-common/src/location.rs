@@ -0,0 +1,129 @@
+use {
+    enum_iterator::Sequence,
+    proc_macro2::TokenStream,
+    quote::{
+        ToTokens,
+        quote,
+    },
+    syn::{
+        LitStr,
+        parse::{
+            Parse,
+            ParseStream,
+        },
+    },
+    crate::Item,
+};
+
+/// How a location gives its item, which determines how it's patched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+pub enum LocationKind {
+    Chest,
+    /// Freestanding items, e.g. pieces of heart.
+    Collectable,
+    BossHeart,
+    Npc,
+    Song,
+    Cutscene,
+    /// The reward for defeating a dungeon boss, given in the blue warp cutscene.
+    Boss,
+    GoldSkulltula,
+    Shop,
+}
+
+macro_rules! locations {
+    ($($variant:ident: $name:literal, $kind:ident, $vanilla_item:ident, $scene:expr, $flag:expr;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
+        pub enum Location {
+            $($variant,)*
+        }
+
+        impl Location {
+            /// The location's name as used in the Python randomizer's spoiler logs and plandos.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            pub fn kind(&self) -> LocationKind {
+                match self {
+                    $(Self::$variant => LocationKind::$kind,)*
+                }
+            }
+
+            pub fn vanilla_item(&self) -> Item {
+                match self {
+                    $(Self::$variant => Item::$vanilla_item,)*
+                }
+            }
+
+            /// The scene in which this location's flag is stored, or `None` for locations that aren't tied to a scene.
+            pub fn scene(&self) -> Option<u8> {
+                match self {
+                    $(Self::$variant => $scene,)*
+                }
+            }
+
+            /// The chest, collectable, or event flag used to identify this location when patching, depending on its kind.
+            pub fn flag(&self) -> Option<u8> {
+                match self {
+                    $(Self::$variant => $flag,)*
+                }
+            }
+        }
+
+        impl Parse for Location {
+            fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+                let lit = input.parse::<LitStr>()?;
+                Ok(match &*lit.value() {
+                    $($name => Self::$variant,)*
+                    name => return Err(syn::Error::new(lit.span(), format!("expected location, found string literal {name:?}"))),
+                })
+            }
+        }
+
+        impl ToTokens for Location {
+            fn to_tokens(&self, tokens: &mut TokenStream) {
+                let stream = match self {
+                    $(Self::$variant => quote!(Location::$variant),)*
+                };
+                stream.to_tokens(tokens);
+            }
+        }
+    };
+}
+
+locations! {
+    // Kokiri Forest
+    KfKokiriSwordChest: "KF Kokiri Sword Chest", Chest, KokiriSword, Some(0x55), Some(0x00);
+    KfMidosTopLeftChest: "KF Midos Top Left Chest", Chest, Rupees5, Some(0x28), Some(0x00);
+    KfMidosTopRightChest: "KF Midos Top Right Chest", Chest, Rupees5, Some(0x28), Some(0x01);
+    KfMidosBottomLeftChest: "KF Midos Bottom Left Chest", Chest, Rupee1, Some(0x28), Some(0x02);
+    KfMidosBottomRightChest: "KF Midos Bottom Right Chest", Chest, RecoveryHeart, Some(0x28), Some(0x03);
+    KfStormsGrottoChest: "KF Storms Grotto Chest", Chest, Rupees20, Some(0x3e), Some(0x0c);
+    KfLinksHouseCow: "KF Links House Cow", Npc, BottleWithMilk, Some(0x34), Some(0x15);
+    KfGsKnowItAllHouse: "KF GS Know It All House", GoldSkulltula, GoldSkulltulaToken, Some(0x0c), Some(0x02);
+    KfGsBeanPatch: "KF GS Bean Patch", GoldSkulltula, GoldSkulltulaToken, Some(0x0c), Some(0x01);
+    KfGsHouseOfTwins: "KF GS House of Twins", GoldSkulltula, GoldSkulltulaToken, Some(0x0c), Some(0x04);
+    KfShopItem1: "KF Shop Item 1", Shop, DekuShield, Some(0x2d), Some(0x30);
+    // boss hearts
+    DekuTreeQueenGohmaHeart: "Deku Tree Queen Gohma Heart", BossHeart, HeartContainer, Some(0x11), Some(0x4f);
+    DodongosCavernKingDodongoHeart: "Dodongos Cavern King Dodongo Heart", BossHeart, HeartContainer, Some(0x12), Some(0x4f);
+    JabuJabusBellyBarinadeHeart: "Jabu Jabus Belly Barinade Heart", BossHeart, HeartContainer, Some(0x13), Some(0x4f);
+    ForestTemplePhantomGanonHeart: "Forest Temple Phantom Ganon Heart", BossHeart, HeartContainer, Some(0x14), Some(0x4f);
+    FireTempleVolvagiaHeart: "Fire Temple Volvagia Heart", BossHeart, HeartContainer, Some(0x15), Some(0x4f);
+    WaterTempleMorphaHeart: "Water Temple Morpha Heart", BossHeart, HeartContainer, Some(0x16), Some(0x4f);
+    SpiritTempleTwinrovaHeart: "Spirit Temple Twinrova Heart", BossHeart, HeartContainer, Some(0x17), Some(0x4f);
+    ShadowTempleBongoBongoHeart: "Shadow Temple Bongo Bongo Heart", BossHeart, HeartContainer, Some(0x18), Some(0x4f);
+    // dungeon rewards
+    QueenGohma: "Queen Gohma", Boss, KokiriEmerald, None, Some(0x6c);
+    KingDodongo: "King Dodongo", Boss, GoronRuby, None, Some(0x6d);
+    Barinade: "Barinade", Boss, ZoraSapphire, None, Some(0x6e);
+    PhantomGanon: "Phantom Ganon", Boss, ForestMedallion, None, Some(0x66);
+    Volvagia: "Volvagia", Boss, FireMedallion, None, Some(0x67);
+    Morpha: "Morpha", Boss, WaterMedallion, None, Some(0x68);
+    Twinrova: "Twinrova", Boss, SpiritMedallion, None, Some(0x69);
+    BongoBongo: "Bongo Bongo", Boss, ShadowMedallion, None, Some(0x6a);
+    LinksPocket: "Links Pocket", Boss, LightMedallion, None, None;
+}
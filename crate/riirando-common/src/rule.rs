@@ -0,0 +1,352 @@
+use {
+    std::{
+        fmt,
+        ops::Not,
+    },
+    convert_case::{
+        Case,
+        Casing as _,
+    },
+    proc_macro2::{
+        Span,
+        TokenStream,
+    },
+    quote::{
+        ToTokens,
+        quote,
+    },
+    syn::Ident,
+    crate::{
+        Item,
+        Savewarp,
+        Trick,
+    },
+};
+
+/// A comparison operator in an access rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    /// The operator as written in Rust and Python.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+
+    pub fn eval<T: Ord>(&self, left: T, right: T) -> bool {
+        match self {
+            Self::Eq => left == right,
+            Self::Ne => left != right,
+            Self::Lt => left < right,
+            Self::Le => left <= right,
+            Self::Gt => left > right,
+            Self::Ge => left >= right,
+        }
+    }
+}
+
+impl ToTokens for CmpOp {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let stream = match self {
+            Self::Eq => quote!(==),
+            Self::Ne => quote!(!=),
+            Self::Lt => quote!(<),
+            Self::Le => quote!(<=),
+            Self::Gt => quote!(>),
+            Self::Ge => quote!(>=),
+        };
+        stream.to_tokens(tokens);
+    }
+}
+
+/// A number in an access rule, e.g. an item count.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Count {
+    Literal(u8),
+    /// The value of a numeric setting.
+    Setting(String),
+}
+
+impl Count {
+    fn to_python(&self) -> String {
+        match self {
+            Self::Literal(count) => count.to_string(),
+            Self::Setting(name) => name.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Count {
+    /// Renders the count as written in access expressions.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(count) => count.fmt(f),
+            Self::Setting(name) => write!(f, "setting({name})"),
+        }
+    }
+}
+
+impl ToTokens for Count {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let stream = match self {
+            Self::Literal(count) => quote!(#count),
+            Self::Setting(name) => {
+                let name = Ident::new(name, Span::call_site());
+                quote!(settings.#name)
+            }
+        };
+        stream.to_tokens(tokens);
+    }
+}
+
+/// An access expression with helpers expanded and all names resolved.
+///
+/// This is shared by the logic compiled in using `riirando_macros::regions!` and logic interpreted at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Rule {
+    Const(bool),
+    And(Box<Rule>, Box<Rule>),
+    Or(Box<Rule>, Box<Rule>),
+    Not(Box<Rule>),
+    IsAdult,
+    IsChild,
+    AtDay,
+    AtNight,
+    AtDampeTime,
+    /// Compares the number of copies of an item in the inventory to a number.
+    Item(Item, CmpOp, Count),
+    /// Checks whether at least the given number of the given items are in the inventory, e.g. for `has_medallions`.
+    CountOf(Vec<Item>, Count),
+    Event(String),
+    Trick(Trick),
+    BoolSetting(String),
+    NumberSetting(String, CmpOp, Count),
+    EnumSetting {
+        name: String,
+        type_name: &'static str,
+        op: CmpOp,
+        variant: String,
+    },
+    /// Checks the current savewarp. Used for the savewarp exits from the root region.
+    Savewarp(Savewarp),
+}
+
+pub(crate) const STONES: [Item; 3] = [Item::KokiriEmerald, Item::GoronRuby, Item::ZoraSapphire];
+pub(crate) const MEDALLIONS: [Item; 6] = [Item::ForestMedallion, Item::FireMedallion, Item::WaterMedallion, Item::SpiritMedallion, Item::ShadowMedallion, Item::LightMedallion];
+pub(crate) const DUNGEON_REWARDS: [Item; 9] = [Item::KokiriEmerald, Item::GoronRuby, Item::ZoraSapphire, Item::ForestMedallion, Item::FireMedallion, Item::WaterMedallion, Item::SpiritMedallion, Item::ShadowMedallion, Item::LightMedallion];
+
+/// How tightly an operator in a rendered rule binds, used to decide where parentheses are needed.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Or,
+    And,
+    Compare,
+    Not,
+}
+
+/// Escapes an item name the way the Python randomizer's rule parser does, e.g. `Progressive_Hookshot`.
+pub fn python_item(item: Item) -> String {
+    item.name().chars().filter(|&c| !matches!(c, '(' | ')' | '\'')).map(|c| if c == ' ' { '_' } else { c }).collect()
+}
+
+fn parenthesize_if(condition: bool, expr: String) -> String {
+    if condition { format!("({expr})") } else { expr }
+}
+
+/// The helper which is lowered to [`Rule::CountOf`] with the given items.
+fn count_of_helper(items: &[Item]) -> Option<&'static str> {
+    if items == STONES {
+        Some("has_stones")
+    } else if items == MEDALLIONS {
+        Some("has_medallions")
+    } else if items == DUNGEON_REWARDS {
+        Some("has_dungeon_rewards")
+    } else {
+        None
+    }
+}
+
+impl Rule {
+    /// Checks whether at least `count` copies of the item are in the inventory.
+    pub fn has(item: Item, count: u8) -> Self {
+        Self::Item(item, CmpOp::Ge, Count::Literal(count))
+    }
+
+    /// Combines two rules using `&&`, simplifying constants.
+    pub fn and(left: Self, right: Self) -> Self {
+        match (left, right) {
+            (Self::Const(false), _) | (_, Self::Const(false)) => Self::Const(false),
+            (Self::Const(true), rule) | (rule, Self::Const(true)) => rule,
+            (left, right) => Self::And(Box::new(left), Box::new(right)),
+        }
+    }
+
+    /// Combines two rules using `||`, simplifying constants.
+    pub fn or(left: Self, right: Self) -> Self {
+        match (left, right) {
+            (Self::Const(true), _) | (_, Self::Const(true)) => Self::Const(true),
+            (Self::Const(false), rule) | (rule, Self::Const(false)) => rule,
+            (left, right) => Self::Or(Box::new(left), Box::new(right)),
+        }
+    }
+
+    /// Renders this rule using the syntax of the Python randomizer's logic files, or returns `None` if it has no equivalent there, e.g. the hardcoded savewarp exits from Root.
+    pub fn to_python(&self) -> Option<String> {
+        self.python_expr(Precedence::Or)
+    }
+
+    /// Renders the rule as a Python expression to be used as an operand of an operator with the given precedence.
+    fn python_expr(&self, context: Precedence) -> Option<String> {
+        Some(match self {
+            Self::Const(true) => "True".to_owned(),
+            Self::Const(false) => "False".to_owned(),
+            Self::And(left, right) => parenthesize_if(context > Precedence::And, format!("{} and {}", left.python_expr(Precedence::And)?, right.python_expr(Precedence::And)?)),
+            Self::Or(left, right) => parenthesize_if(context > Precedence::Or, format!("{} or {}", left.python_expr(Precedence::Or)?, right.python_expr(Precedence::Or)?)),
+            Self::Not(rule) => format!("not {}", rule.python_expr(Precedence::Not)?),
+            Self::IsAdult => "is_adult".to_owned(),
+            Self::IsChild => "is_child".to_owned(),
+            Self::AtDay => "at_day".to_owned(),
+            Self::AtNight => "at_night".to_owned(),
+            Self::AtDampeTime => "at_dampe_time".to_owned(),
+            Self::Item(item, CmpOp::Ge, Count::Literal(1)) => python_item(*item),
+            Self::Item(item, CmpOp::Ge, count) => format!("({}, {})", python_item(*item), count.to_python()),
+            Self::Item(item, CmpOp::Lt, count) => format!("not ({}, {})", python_item(*item), count.to_python()),
+            // the Python randomizer can only check for a minimum item count, so other comparisons are only supported with literal counts
+            Self::Item(item, op, Count::Literal(count)) => {
+                let item = python_item(*item);
+                let next = count.checked_add(1)?;
+                match op {
+                    CmpOp::Eq => parenthesize_if(context > Precedence::And, format!("({item}, {count}) and not ({item}, {next})")),
+                    CmpOp::Ne => parenthesize_if(context > Precedence::Or, format!("not ({item}, {count}) or ({item}, {next})")),
+                    CmpOp::Le => format!("not ({item}, {next})"),
+                    CmpOp::Gt => format!("({item}, {next})"),
+                    CmpOp::Lt | CmpOp::Ge => unreachable!("handled above"),
+                }
+            }
+            Self::Item(_, _, Count::Setting(_)) => return None,
+            Self::CountOf(items, count) => format!("{}({})", count_of_helper(items)?, count.to_python()),
+            Self::Event(name) => format!("'{name}'"),
+            Self::Trick(trick) => format!("logic_{}", trick.id()),
+            Self::BoolSetting(name) => name.clone(),
+            Self::NumberSetting(name, op, count) => format!("{name} {} {}", op.symbol(), count.to_python()),
+            Self::EnumSetting { name, type_name: _, op, variant } => format!("{name} {} '{}'", op.symbol(), variant.to_case(Case::Snake)),
+            Self::Savewarp(_) => return None,
+        })
+    }
+
+    /// Renders the rule as an access expression to be used as an operand of an operator with the given precedence.
+    fn access_expr(&self, context: Precedence) -> String {
+        match self {
+            Self::Const(value) => value.to_string(),
+            Self::And(left, right) => parenthesize_if(context > Precedence::And, format!("{} && {}", left.access_expr(Precedence::And), right.access_expr(Precedence::And))),
+            Self::Or(left, right) => parenthesize_if(context > Precedence::Or, format!("{} || {}", left.access_expr(Precedence::Or), right.access_expr(Precedence::Or))),
+            Self::Not(rule) => format!("!{}", rule.access_expr(Precedence::Not)),
+            Self::IsAdult => "is_adult".to_owned(),
+            Self::IsChild => "is_child".to_owned(),
+            Self::AtDay => "at_day".to_owned(),
+            Self::AtNight => "at_night".to_owned(),
+            Self::AtDampeTime => "at_dampe_time".to_owned(),
+            Self::Item(item, CmpOp::Ge, Count::Literal(1)) => format!("{item:?}"),
+            Self::Item(item, CmpOp::Ge, count) => format!("{item:?}({count})"),
+            Self::Item(item, op, count) => parenthesize_if(context > Precedence::Compare, format!("{item:?} {} {count}", op.symbol())),
+            Self::CountOf(items, count) => match count_of_helper(items) {
+                Some(helper) => format!("{helper}({count})"),
+                None => format!("count_of({items:?}, {count})"),
+            },
+            Self::Event(name) => format!("{name:?}"),
+            Self::Trick(trick) => format!("trick({})", trick.id()),
+            Self::BoolSetting(name) => format!("setting({name})"),
+            Self::NumberSetting(name, op, count) => parenthesize_if(context > Precedence::Compare, format!("setting({name}) {} {count}", op.symbol())),
+            Self::EnumSetting { name, type_name: _, op, variant } => parenthesize_if(context > Precedence::Compare, format!("setting({name}) {} {variant}", op.symbol())),
+            Self::Savewarp(savewarp) => format!("savewarp({savewarp})"),
+        }
+    }
+
+    /// Whether this rule compiles to a Rust expression that doesn't need parentheses when negated.
+    fn is_atomic(&self) -> bool {
+        matches!(self,
+            Self::Const(_) | Self::AtDay | Self::AtNight | Self::Event(_) | Self::Trick(_) | Self::BoolSetting(_) | Self::Not(_)
+            | Self::Item(_, CmpOp::Ge, Count::Literal(1))
+        )
+    }
+}
+
+impl Not for Rule {
+    type Output = Self;
+
+    /// Negates a rule, simplifying constants and double negation.
+    fn not(self) -> Self {
+        match self {
+            Self::Const(value) => Self::Const(!value),
+            Self::Not(rule) => *rule,
+            rule => Self::Not(Box::new(rule)),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Renders the rule in the syntax of access expressions, with helpers expanded.
+    ///
+    /// Savewarp checks, which can't be written in logic files, are rendered as `savewarp(...)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.access_expr(Precedence::Or))
+    }
+}
+
+impl ToTokens for Rule {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let stream = match self {
+            Self::Const(value) => quote!(#value),
+            Self::And(left, right) => {
+                let left = if let Self::Or(..) = **left { quote!((#left)) } else { quote!(#left) };
+                let right = if let Self::Or(..) = **right { quote!((#right)) } else { quote!(#right) };
+                quote!(#left && #right)
+            }
+            Self::Or(left, right) => quote!(#left || #right),
+            Self::Not(rule) => if rule.is_atomic() { quote!(!#rule) } else { quote!(!(#rule)) },
+            Self::IsAdult => quote!(state.age == Age::Adult),
+            Self::IsChild => quote!(state.age == Age::Child),
+            Self::AtDay => quote!(state.time_of_day.is_day()),
+            Self::AtNight => quote!(state.time_of_day.is_night()),
+            Self::AtDampeTime => quote!(state.time_of_day == TimeOfDay::Dampe),
+            Self::Item(item, CmpOp::Ge, Count::Literal(1)) => quote!(inventory.contains(world, #item)),
+            Self::Item(item, op, count) => quote!(inventory.count(world, #item) #op #count),
+            Self::CountOf(items, count) => quote!([#(#items),*].into_iter().filter(|&item| inventory.contains(world, item)).count() >= usize::from(#count)),
+            Self::Event(name) => {
+                let event_ident = Ident::new(&name.to_case(Case::Pascal), Span::call_site());
+                quote!(inventory.has_event(world, Event::#event_ident))
+            }
+            Self::Trick(trick) => quote!(settings.tricks.contains(&#trick)),
+            Self::BoolSetting(name) => {
+                let name = Ident::new(name, Span::call_site());
+                quote!(settings.#name)
+            }
+            Self::NumberSetting(name, op, count) => {
+                let name = Ident::new(name, Span::call_site());
+                quote!(settings.#name #op #count)
+            }
+            Self::EnumSetting { name, type_name, op, variant } => {
+                let name = Ident::new(name, Span::call_site());
+                let type_name = Ident::new(type_name, Span::call_site());
+                let variant = Ident::new(variant, Span::call_site());
+                quote!(settings.#name #op #type_name::#variant)
+            }
+            Self::Savewarp(savewarp) => quote!(state.savewarp == #savewarp),
+        };
+        stream.to_tokens(tokens);
+    }
+}
@@ -0,0 +1,138 @@
+use {
+    std::collections::BTreeSet,
+    enum_iterator::Sequence,
+    crate::Trick,
+};
+
+/// The type of a setting, used to check setting references in logic files at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Bool,
+    Number,
+    Enum {
+        type_name: &'static str,
+        variants: &'static [&'static str],
+    },
+}
+
+macro_rules! settings {
+    (
+        bools {
+            $($bool_name:ident: $bool_default:literal,)*
+        }
+        numbers {
+            $($number_name:ident: $number_default:literal,)*
+        }
+        enums {
+            $($enum_name:ident: $enum_type:ident = $enum_default:ident { $($variant:ident,)* },)*
+        }
+    ) => {
+        $(
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+            pub enum $enum_type {
+                $($variant,)*
+            }
+
+            impl Default for $enum_type {
+                fn default() -> Self {
+                    Self::$enum_default
+                }
+            }
+        )*
+
+        /// The settings for a single world.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct Settings {
+            $(pub $bool_name: bool,)*
+            $(pub $number_name: u8,)*
+            $(pub $enum_name: $enum_type,)*
+            /// The tricks which may be required by logic in this world.
+            pub tricks: BTreeSet<Trick>,
+        }
+
+        impl Default for Settings {
+            fn default() -> Self {
+                Self {
+                    $($bool_name: $bool_default,)*
+                    $($number_name: $number_default,)*
+                    $($enum_name: $enum_type::$enum_default,)*
+                    tricks: BTreeSet::default(),
+                }
+            }
+        }
+
+        impl Settings {
+            /// Returns the type of the setting with the given name, or `None` if no such setting exists.
+            pub fn kind(name: &str) -> Option<SettingKind> {
+                match name {
+                    $(stringify!($bool_name) => Some(SettingKind::Bool),)*
+                    $(stringify!($number_name) => Some(SettingKind::Number),)*
+                    $(stringify!($enum_name) => Some(SettingKind::Enum {
+                        type_name: stringify!($enum_type),
+                        variants: &[$(stringify!($variant),)*],
+                    }),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the value of the boolean setting with the given name, for use by the logic interpreter.
+            pub fn bool_setting(&self, name: &str) -> Option<bool> {
+                match name {
+                    $(stringify!($bool_name) => Some(self.$bool_name),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the value of the numeric setting with the given name, for use by the logic interpreter.
+            pub fn number_setting(&self, name: &str) -> Option<u8> {
+                match name {
+                    $(stringify!($number_name) => Some(self.$number_name),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the name of the selected variant of the enum setting with the given name, for use by the logic interpreter.
+            pub fn enum_setting(&self, name: &str) -> Option<&'static str> {
+                match name {
+                    $(stringify!($enum_name) => Some(match self.$enum_name {
+                        $($enum_type::$variant => stringify!($variant),)*
+                    }),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+settings! {
+    bools {
+        open_door_of_time: true,
+        shuffle_dungeon_entrances: false,
+    }
+    numbers {
+        bridge_stones: 3,
+        bridge_medallions: 6,
+        bridge_rewards: 9,
+        bridge_tokens: 100,
+    }
+    enums {
+        open_forest: OpenForest = Closed {
+            Open,
+            ClosedDeku,
+            Closed,
+        },
+        starting_age: StartingAge = Child {
+            Child,
+            Adult,
+            Random,
+        },
+        bridge: Bridge = Open {
+            Open,
+            Vanilla,
+            Stones,
+            Medallions,
+            Dungeons,
+            Tokens,
+        },
+    }
+}
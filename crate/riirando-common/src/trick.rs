@@ -0,0 +1,76 @@
+use {
+    enum_iterator::Sequence,
+    proc_macro2::TokenStream,
+    quote::{
+        ToTokens,
+        quote,
+    },
+};
+
+/// How hard a trick is to perform, used to group tricks in the GUI and in presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
+pub enum TrickDifficulty {
+    Novice,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+macro_rules! tricks {
+    ($($variant:ident: $id:literal, $name:literal, $difficulty:ident, $description:literal;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
+        pub enum Trick {
+            $($variant,)*
+        }
+
+        impl Trick {
+            /// Returns the trick with the given ID, as used in `trick(...)` access expressions.
+            pub fn from_id(id: &str) -> Option<Self> {
+                match id {
+                    $($id => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            pub fn id(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $id,)*
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            pub fn difficulty(&self) -> TrickDifficulty {
+                match self {
+                    $(Self::$variant => TrickDifficulty::$difficulty,)*
+                }
+            }
+
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $description,)*
+                }
+            }
+        }
+
+        impl ToTokens for Trick {
+            fn to_tokens(&self, tokens: &mut TokenStream) {
+                let stream = match self {
+                    $(Self::$variant => quote!(Trick::$variant),)*
+                };
+                stream.to_tokens(tokens);
+            }
+        }
+    };
+}
+
+tricks! {
+    LensWasteland: "lens_wasteland", "Haunted Wasteland without Lens of Truth", Novice, "By memorizing the path, you can travel through the Wasteland without using the Lens of Truth to see the Poe. The equivalent trick for going in reverse through the Wasteland is \"Reverse Wasteland\".";
+    ShadowFireArrowEntry: "shadow_fire_arrow_entry", "Shadow Temple Entry with Fire Arrows", Intermediate, "It is possible to light all of the torches to open the Shadow Temple entrance with just Fire Arrows, but you must be very quick, precise, and strategic with how you take your shots.";
+    DcJump: "dc_jump", "Dodongo's Cavern Spike Trap Room Jump without Hover Boots", Novice, "The jump is adult Link only. Applies to both vanilla and MQ.";
+    VisibleCollisions: "visible_collisions", "Pass Through Visible One-Way Collisions", Novice, "Allows climbing through the platform to reach Impa's House Back as adult with no items and going through the Kakariko Village Gate as child when coming from the Mountain Trail side.";
+}
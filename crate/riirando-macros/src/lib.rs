@@ -1,10 +1,12 @@
 use {
-    std::fs,
+    std::{
+        collections::BTreeSet,
+        fs,
+    },
     convert_case::{
         Case,
         Casing as _,
     },
-    enum_iterator::all,
     proc_macro2::{
         Span,
         TokenStream,
@@ -16,77 +18,138 @@ use {
         Variant,
         parse_quote,
     },
-    riirando_common::Savewarp,
-    crate::ast::*,
+    riirando_common::{
+        Rule,
+        ast::{
+            LoadError,
+            LogicSet,
+            Region,
+        },
+    },
 };
 
-mod ast;
-
-#[derive(Debug, thiserror::Error)]
-enum Error {
-    #[error(transparent)] Io(#[from] std::io::Error),
-    #[error(transparent)] Syn(#[from] syn::Error),
-}
-
-impl Error {
-    fn into_compile_error(self) -> TokenStream {
-        match self {
-            Self::Io(e) => {
-                let msg = e.to_string();
-                quote! {
-                    compile_error!(#msg);
-                }
+fn into_compile_error(e: LoadError) -> TokenStream {
+    match e {
+        LoadError::Io(e) => {
+            let msg = e.to_string();
+            quote! {
+                compile_error!(#msg);
             }
-            Self::Syn(e) => e.into_compile_error(),
         }
+        LoadError::Syn(e) => e.into_compile_error(),
+        LoadError::Logic(msgs) => quote! {
+            #(compile_error!(#msgs);)*
+        },
     }
 }
 
-fn regions_inner() -> Result<TokenStream, Error> {
+fn compiled_access(rule: &Rule) -> TokenStream {
+    let python = match rule.to_python() {
+        Some(python) => quote!(Some(#python)),
+        None => quote!(None),
+    };
+    let source = rule.to_string();
+    quote!(CompiledAccess { can_access: |world, state, settings, inventory| #rule, source: #source, python: #python })
+}
+
+fn regions_inner() -> Result<TokenStream, LoadError> {
+    let LogicSet { regions } = LogicSet::load(&fs::canonicalize(concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/logic"))?)?;
     let mut variants = Vec::<Variant>::default();
+    let mut event_names = BTreeSet::<String>::default();
+    let mut name_arms = Vec::<Arm>::default();
+    let mut from_name_arms = Vec::<Arm>::default();
     let mut info_arms = Vec::<Arm>::default();
-    for res in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/logic"))? {
-        let LogicFile { regions } = syn::parse_str(&fs::read_to_string(res?.path())?)?;
-        for (name, RegionInfo { savewarp, time_of_day, items, exits }) in regions {
-            let variant_name = name.to_case(Case::Pascal);
-            let variant_ident = Ident::new(&variant_name, Span::call_site());
-            variants.push(parse_quote!(#variant_ident));
-            let savewarp = savewarp.unwrap_or_else(|| Savewarp::Overworld); //TODO automatically assign dungeon savewarps once dungeons are split into individual logic files
-            let items = items.into_iter()
-                .map(|(item, Access(access))| quote!(#item => (|state, inventory| #access) as Access));
-            let exits = exits.into_iter()
-                .map(|(target_region, Access(access))| {
-                    let target_variant = target_region.to_case(Case::Pascal);
-                    let target_ident = Ident::new(&target_variant, Span::call_site());
-                    quote!(Self::#target_ident => (|state, inventory| #access) as Access)
-                })
-                .chain((name == "Root").then(|| all::<Savewarp>().map(|savewarp| {
-                    let target_variant = savewarp.to_string();
-                    let target_ident = Ident::new(&target_variant, Span::call_site());
-                    quote!(Self::#target_ident => (|state, inventory| state.savewarp == #savewarp) as Access)
-                })).into_iter().flatten());
-            info_arms.push(parse_quote! {
-                Self::#variant_ident => RegionInfo {
-                    savewarp: #savewarp,
-                    time_of_day: #time_of_day,
-                    items: collect![
-                        #(#items,)*
-                    ],
-                    exits: collect![
-                        #(#exits,)*
-                    ],
-                },
-            });
+    for (name, Region { savewarp, time_of_day, time_travel, refill, farores_wind, locations, events, exits, damage }) in regions {
+        let variant_name = name.to_case(Case::Pascal);
+        let variant_ident = Ident::new(&variant_name, Span::call_site());
+        variants.push(parse_quote!(#variant_ident));
+        name_arms.push(parse_quote!(Self::#variant_ident => #name,));
+        from_name_arms.push(parse_quote!(#name => Some(Self::#variant_ident),));
+        let location_arms = locations.into_iter().map(|(location, rule)| {
+            let access = compiled_access(&rule);
+            quote!(#location => #access)
+        });
+        let mut event_arms = Vec::with_capacity(events.len());
+        for (event_name, rule) in events {
+            let event_ident = Ident::new(&event_name.to_case(Case::Pascal), Span::call_site());
+            let access = compiled_access(&rule);
+            event_names.insert(event_name);
+            event_arms.push(quote!(Event::#event_ident => #access));
         }
+        let exit_arms = exits.into_iter().map(|(target_region, rule)| {
+            let target_ident = Ident::new(&target_region.to_case(Case::Pascal), Span::call_site());
+            let access = compiled_access(&rule);
+            quote!(Self::#target_ident => #access)
+        });
+        let damage_arms = damage.into_iter().map(|(target_region, hearts)| {
+            let target_ident = Ident::new(&target_region.to_case(Case::Pascal), Span::call_site());
+            quote!(Self::#target_ident => #hearts)
+        });
+        let refill = match refill {
+            Some(refill) => quote!(Some(#refill)),
+            None => quote!(None),
+        };
+        info_arms.push(parse_quote! {
+            Self::#variant_ident => RegionInfo {
+                savewarp: #savewarp,
+                time_of_day: #time_of_day,
+                time_travel: #time_travel,
+                refill: #refill,
+                farores_wind: #farores_wind,
+                locations: collect![
+                    #(#location_arms,)*
+                ],
+                events: collect![
+                    #(#event_arms,)*
+                ],
+                exits: collect![
+                    #(#exit_arms,)*
+                ],
+                damage: collect![
+                    #(#damage_arms,)*
+                ],
+            },
+        });
     }
+    let event_variants = event_names.iter()
+        .map(|event_name| Ident::new(&event_name.to_case(Case::Pascal), Span::call_site()))
+        .collect::<Vec<_>>();
     Ok(quote! {
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
         pub(crate) enum Region {
             #(#variants,)*
         }
 
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub(crate) enum Event {
+            #(#event_variants,)*
+        }
+
+        impl Event {
+            /// The event's name as used in the logic files.
+            pub(crate) fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#event_variants => #event_names,)*
+                }
+            }
+        }
+
         impl Region {
-            pub(crate) fn info(&self) -> RegionInfo {
+            pub(crate) fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #(#from_name_arms)*
+                    _ => None,
+                }
+            }
+
+            /// The region's name as used in the logic files.
+            pub(crate) fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms)*
+                }
+            }
+
+            pub(crate) fn info(&self) -> RegionInfo<CompiledLogic> {
                 match self {
                     #(#info_arms)*
                 }
@@ -102,8 +165,21 @@ pub fn regions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             compile_error!("riirando_macros::impl_region does not take parameters");
         }.into()
     }
-    match regions_inner() {
-        Ok(output) => output.into(),
-        Err(e) => e.into_compile_error().into(),
+    // Logic files are parsed using proc-macro2's fallback implementation since spans of compiler tokens parsed from a string don't carry byte offsets into it.
+    // The output is converted back to compiler tokens via its string representation.
+    proc_macro2::fallback::force();
+    let output = match regions_inner() {
+        Ok(output) => output,
+        Err(e) => into_compile_error(e),
+    }.to_string();
+    proc_macro2::fallback::unforce();
+    match output.parse() {
+        Ok(output) => output,
+        Err(e) => {
+            let msg = format!("failed to convert regions! output to compiler tokens: {e}");
+            quote! {
+                compile_error!(#msg);
+            }.into()
+        }
     }
 }
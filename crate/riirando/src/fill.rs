@@ -0,0 +1,665 @@
+//! Fill algorithms which place items and entrances. The default is the matrix-based one described in `assets/doc/fill.md`, the others are for comparison.
+
+use {
+    std::{
+        collections::{
+            BTreeSet,
+            HashMap,
+        },
+        hash::Hash,
+        num::{
+            NonZeroU32,
+            NonZeroUsize,
+        },
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+    enum_iterator::{
+        Sequence,
+        all,
+    },
+    itertools::Itertools as _,
+    rand::prelude::*,
+    riirando_common::{
+        *,
+        ast::savewarp_region,
+    },
+    crate::{
+        logic::{
+            Logic,
+            RegionInfo,
+        },
+        search::{
+            self,
+            Inventory,
+            Placement,
+            RegionAccess,
+        },
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error("the logic doesn't have exactly one entrance into {0} from the overworld and one exit back out, as required for dungeon entrance shuffle")]
+    DungeonEntrance(Dungeon),
+    #[error("the settings can't be filled: reached a dead end with no random choices left to roll back")]
+    Impossible,
+    #[error("gave up filling after {0} failed attempts")]
+    Attempts(usize),
+    #[error(transparent)] Search(#[from] search::Error),
+}
+
+/// Resolves settings which are chosen randomly, such as [`StartingAge::Random`]. This must be done before filling or searching the worlds.
+pub(crate) fn finalize_settings(worlds: &mut [Settings], rng: &mut impl Rng) {
+    for settings in worlds {
+        if let StartingAge::Random = settings.starting_age {
+            settings.starting_age = if rng.random() { StartingAge::Adult } else { StartingAge::Child };
+        }
+    }
+}
+
+/// Statistics about a fill, for comparing strategies, see [`benchmark`].
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Stats {
+    /// How often the strategy had to roll back a choice or start over.
+    pub(crate) retries: usize,
+}
+
+/// An algorithm for placing items and entrances in all worlds such that each world can be beaten.
+pub(crate) trait Strategy {
+    fn fill<L: Logic>(&self, logic: &L, worlds: &[Settings], rng: &mut impl Rng, stats: &mut Stats) -> Result<Vec<Placement<L::Region>>, Error>;
+}
+
+/// The fill strategies which can be selected with `--fill-algorithm`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Sequence, clap::ValueEnum)]
+pub(crate) enum Algorithm {
+    /// Places items and entrances in a single pass, see `assets/doc/fill.md`.
+    #[default]
+    Matrix,
+    /// The assumed fill used by the Python randomizer.
+    Assumed,
+    /// A simple forward fill.
+    Forward,
+}
+
+/// The fill options which can be configured on the command line.
+#[derive(Debug, Clone, Copy, clap::Args)]
+#[clap(about = None, long_about = None)] // don't use the doc comment as the program's description
+pub(crate) struct Options {
+    /// The alternatives to the default are meant for comparison, see the `benchmark-fill` subcommand.
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) fill_algorithm: Algorithm,
+    /// How many times the fill algorithms which start over on failure (assumed and forward) try before giving up.
+    #[clap(long, default_value_t = DEFAULT_MAX_ATTEMPTS)]
+    pub(crate) max_fill_attempts: NonZeroUsize,
+}
+
+impl Strategy for Options {
+    fn fill<L: Logic>(&self, logic: &L, worlds: &[Settings], rng: &mut impl Rng, stats: &mut Stats) -> Result<Vec<Placement<L::Region>>, Error> {
+        match self.fill_algorithm {
+            Algorithm::Matrix => MatrixFill.fill(logic, worlds, rng, stats),
+            Algorithm::Assumed => AssumedFill { max_attempts: self.max_fill_attempts }.fill(logic, worlds, rng, stats),
+            Algorithm::Forward => ForwardFill { max_attempts: self.max_fill_attempts }.fill(logic, worlds, rng, stats),
+        }
+    }
+}
+
+/// Something that can be filled, i.e. a row of the [`Matrix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    Location(Location),
+    /// The entrance from the overworld which leads into this dungeon in vanilla.
+    DungeonEntrance(Dungeon),
+}
+
+/// Something that can be placed into a check, i.e. a column of the [`Matrix`].
+///
+/// Items which appear multiple times in the item pool are tracked as separate fillings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filling {
+    Item(Item),
+    /// This dungeon, entered via the entrance it's placed at.
+    Dungeon(Dungeon),
+}
+
+/// How a dungeon is connected to the overworld in vanilla, see [`dungeon_entrances`].
+struct DungeonEntrance<R> {
+    /// The overworld region with the entrance into the dungeon.
+    outside: R,
+    /// The dungeon's first region, in the dungeon's variant used in the world.
+    entry: R,
+    /// The overworld region which the dungeon's entry exits to.
+    exit: R,
+}
+
+/// Connects the overworld entrance of one dungeon to another dungeon. Dungeon entrances are coupled, so the target dungeon exits to where this dungeon exits to in vanilla.
+fn connect<R: Copy + Eq + Hash>(placement: &mut Placement<R>, entrance: &DungeonEntrance<R>, target: &DungeonEntrance<R>) {
+    placement.entrances.entry((entrance.outside, entrance.entry)).or_default().push(target.entry);
+    placement.entrances.entry((target.entry, target.exit)).or_default().push(entrance.exit);
+}
+
+/// Returns how each dungeon is connected to the overworld in vanilla, if dungeon entrances are shuffled in the world.
+fn dungeon_entrances<L: Logic>(logic: &L, infos: &HashMap<L::Region, RegionInfo<L>>, settings: &Settings) -> Result<HashMap<Dungeon, DungeonEntrance<L::Region>>, Error> {
+    if !settings.shuffle_dungeon_entrances { return Ok(HashMap::default()) }
+    let regions = logic.regions();
+    let region_names = regions.iter().map(|&region| logic.region_name(region).to_owned()).collect_vec();
+    //TODO setting to include Ganon's Castle
+    all::<Dungeon>().filter(|&dungeon| dungeon != Dungeon::InsideGanonsCastle).map(|dungeon| {
+        let entry = savewarp_region(dungeon.savewarp(), &region_names)
+            .and_then(|name| logic.region(name))
+            .ok_or(Error::DungeonEntrance(dungeon))?;
+        // Root's exits to savewarps aren't entrances
+        let outside = regions.iter()
+            .copied()
+            .filter(|&region| region != logic.root() && !infos[&region].savewarp.is_dungeon() && infos[&region].exits.contains_key(&entry))
+            .exactly_one().map_err(|_| Error::DungeonEntrance(dungeon))?;
+        let exit = infos[&entry].exits.keys()
+            .copied()
+            .filter(|target| !infos[target].savewarp.is_dungeon())
+            .exactly_one().map_err(|_| Error::DungeonEntrance(dungeon))?;
+        Ok((dungeon, DungeonEntrance { outside, entry, exit }))
+    }).collect()
+}
+
+/// Whether the item may be placed at the location, depending on their kinds.
+fn can_hold(location: Location, item: Item) -> bool {
+    match location.kind() {
+        LocationKind::Shop => item == location.vanilla_item(), //TODO shopsanity
+        LocationKind::GoldSkulltula => item.kind() == ItemKind::Token, //TODO tokensanity
+        LocationKind::Boss => item.kind() == ItemKind::DungeonReward,
+        LocationKind::Song => item.kind() == ItemKind::Song, //TODO song shuffle
+        //TODO keep dungeon items in their own dungeon
+        LocationKind::Chest | LocationKind::Collectable | LocationKind::BossHeart | LocationKind::Npc | LocationKind::Cutscene => !matches!(item.kind(), ItemKind::Token | ItemKind::DungeonReward | ItemKind::Song),
+    }
+}
+
+/// Returns the locations which appear in the logic. Only these can be filled, and their vanilla items make up the item pool.
+fn locations<L: Logic>(infos: &HashMap<L::Region, RegionInfo<L>>) -> BTreeSet<Location> {
+    infos.values().flat_map(|info| info.locations.keys().copied()).collect()
+}
+
+/// The possible placements across all worlds, with a row for each check and a column for each filling.
+///
+/// Checks and fillings are tagged with their world. Items are only placed within their own world.
+#[derive(Clone)]
+struct Matrix {
+    checks: Vec<(usize, Check)>,
+    fillings: Vec<(usize, Filling)>,
+    /// Whether each filling can still go to each check, indexed by check and then filling.
+    marks: Vec<Vec<bool>>,
+    checks_locked: Vec<bool>,
+    fillings_locked: Vec<bool>,
+}
+
+impl Matrix {
+    fn new<L: Logic>(infos: &HashMap<L::Region, RegionInfo<L>>, dungeon_entrances: &[HashMap<Dungeon, DungeonEntrance<L::Region>>]) -> Self {
+        let locations = locations(infos);
+        let mut checks = Vec::default();
+        let mut fillings = Vec::default();
+        for (world, world_dungeon_entrances) in dungeon_entrances.iter().enumerate() {
+            for &location in &locations {
+                checks.push((world, Check::Location(location)));
+                fillings.push((world, Filling::Item(location.vanilla_item())));
+            }
+            for dungeon in all::<Dungeon>().filter(|dungeon| world_dungeon_entrances.contains_key(dungeon)) {
+                checks.push((world, Check::DungeonEntrance(dungeon)));
+                fillings.push((world, Filling::Dungeon(dungeon)));
+            }
+        }
+        let marks = checks.iter().map(|&(check_world, check)| fillings.iter().map(|&(filling_world, filling)| check_world == filling_world && match (check, filling) {
+            (Check::Location(location), Filling::Item(item)) => can_hold(location, item),
+            (Check::DungeonEntrance(_), Filling::Dungeon(_)) => true,
+            (Check::Location(_), Filling::Dungeon(_)) | (Check::DungeonEntrance(_), Filling::Item(_)) => false,
+        }).collect()).collect();
+        Self {
+            checks_locked: vec![false; checks.len()],
+            fillings_locked: vec![false; fillings.len()],
+            checks, fillings, marks,
+        }
+    }
+
+    /// Picks one of the unlocked rows and columns tied for fewest marked entries, and returns the marked entries in it as (check, filling) index pairs.
+    ///
+    /// Returns `None` if all rows and columns are locked.
+    fn next_options(&self, rng: &mut impl Rng) -> Option<Vec<(usize, usize)>> {
+        let rows = (0..self.checks.len())
+            .filter(|&check| !self.checks_locked[check])
+            .map(|check| (0..self.fillings.len()).filter(|&filling| self.marks[check][filling]).map(|filling| (check, filling)).collect_vec());
+        let columns = (0..self.fillings.len())
+            .filter(|&filling| !self.fillings_locked[filling])
+            .map(|filling| (0..self.checks.len()).filter(|&check| self.marks[check][filling]).map(|check| (check, filling)).collect_vec());
+        rows.chain(columns).min_set_by_key(Vec::len).choose(rng).cloned()
+    }
+
+    /// Locks in the given filling for the given check, removing all other markings from its row and column.
+    fn lock(&mut self, check: usize, filling: usize) {
+        for (iter_filling, mark) in self.marks[check].iter_mut().enumerate() {
+            *mark = iter_filling == filling;
+        }
+        for (iter_check, row) in self.marks.iter_mut().enumerate() {
+            row[filling] = iter_check == check;
+        }
+        self.checks_locked[check] = true;
+        self.fillings_locked[filling] = true;
+    }
+
+    /// Converts the matrix to the placements used by the search, with all marked fillings as options for each check.
+    fn placements<R: Copy + Eq + Hash>(&self, dungeon_entrances: &[HashMap<Dungeon, DungeonEntrance<R>>]) -> Vec<Placement<R>> {
+        let mut placements = dungeon_entrances.iter().map(|_| Placement { items: HashMap::default(), entrances: HashMap::default() }).collect_vec();
+        for (check_idx, &(world, check)) in self.checks.iter().enumerate() {
+            let options = self.marks[check_idx].iter().enumerate().filter(|&(_, &mark)| mark).map(|(filling_idx, _)| self.fillings[filling_idx].1);
+            match check {
+                Check::Location(location) => {
+                    placements[world].items.insert(location, options.map(|filling| match filling {
+                        Filling::Item(item) => item,
+                        Filling::Dungeon(_) => unreachable!("dungeon marked for a location"),
+                    }).unique().collect());
+                }
+                Check::DungeonEntrance(dungeon) => {
+                    let entrance = &dungeon_entrances[world][&dungeon];
+                    for filling in options {
+                        let Filling::Dungeon(target_dungeon) = filling else { unreachable!("item marked for a dungeon entrance") };
+                        connect(&mut placements[world], entrance, &dungeon_entrances[world][&target_dungeon]);
+                    }
+                }
+            }
+        }
+        placements
+    }
+}
+
+/// The fill algorithm described in `assets/doc/fill.md`, which places items and entrances in a single pass using a matrix of possible placements.
+pub(crate) struct MatrixFill;
+
+impl Strategy for MatrixFill {
+    fn fill<L: Logic>(&self, logic: &L, worlds: &[Settings], rng: &mut impl Rng, stats: &mut Stats) -> Result<Vec<Placement<L::Region>>, Error> {
+        let infos = logic.regions().into_iter().map(|region| (region, logic.info(region))).collect::<HashMap<_, _>>();
+        let dungeon_entrances = worlds.iter().map(|settings| dungeon_entrances(logic, &infos, settings)).collect::<Result<Vec<_>, _>>()?;
+        // fail early on unfinalized settings instead of treating every reachability check as a dead end
+        search::starting_ages(worlds)?;
+        let mut matrix = Matrix::new(&infos, &dungeon_entrances);
+        // the matrices before each random choice, along with the choice that was made
+        let mut choices = Vec::<(Matrix, usize, usize)>::default();
+        loop {
+            let options = match matrix.next_options(rng) {
+                Some(options) => options,
+                None => {
+                    let placements = matrix.placements(&dungeon_entrances);
+                    if search::check_reachability(logic, worlds, &placements).is_ok() { return Ok(placements) }
+                    Vec::default()
+                }
+            };
+            let Some(&(check, filling)) = options.choose(rng) else {
+                // dead end, roll back to before the last random choice and disallow the option that was rolled
+                //TODO start over from scratch but remember the disallowed combination of random choices instead, in case the actual problem is near the start
+                let (previous, check, filling) = choices.pop().ok_or(Error::Impossible)?;
+                stats.retries += 1;
+                matrix = previous;
+                matrix.marks[check][filling] = false;
+                continue
+            };
+            let mut locked = matrix.clone();
+            locked.lock(check, filling);
+            // This recomputes which global states are in logic each time, which is expensive.
+            // Reusing them from an earlier search with more options open isn't enough since those are an overestimate,
+            // so a choice which makes a spawn state a dead end wouldn't be noticed until everything is placed, leading to a very long rollback.
+            if search::check_reachability(logic, worlds, &locked.placements(&dungeon_entrances)).is_ok() {
+                // if there was only one option, this doesn't count as a random choice
+                if options.len() > 1 {
+                    choices.push((matrix, check, filling));
+                }
+                matrix = locked;
+            } else {
+                matrix.marks[check][filling] = false;
+            }
+        }
+    }
+}
+
+/// How many times [`AssumedFill`] and [`ForwardFill`] try before giving up, unless configured otherwise.
+const DEFAULT_MAX_ATTEMPTS: NonZeroUsize = NonZeroUsize::new(100).unwrap();
+
+/// An attempt by one of the strategies which shuffle entrances up front and then place items one at a time, starting over on failure.
+struct Attempt<R> {
+    placements: Vec<Placement<R>>,
+    /// The locations in each world which haven't been filled yet.
+    empty: Vec<BTreeSet<Location>>,
+    /// The items in each world which haven't been placed yet.
+    pool: Vec<Vec<Item>>,
+}
+
+impl<R: Copy + Eq + Hash> Attempt<R> {
+    /// Shuffles dungeon entrances and places the items at locations which can only hold one of the items in the pool, e.g. shops.
+    fn new(locations: &BTreeSet<Location>, dungeon_entrances: &[HashMap<Dungeon, DungeonEntrance<R>>], rng: &mut impl Rng) -> Self {
+        let mut attempt = Self {
+            placements: dungeon_entrances.iter().map(|_| Placement { items: HashMap::default(), entrances: HashMap::default() }).collect(),
+            empty: dungeon_entrances.iter().map(|_| locations.clone()).collect(),
+            pool: dungeon_entrances.iter().map(|_| locations.iter().map(|location| location.vanilla_item()).collect()).collect(),
+        };
+        for (world, world_dungeon_entrances) in dungeon_entrances.iter().enumerate() {
+            let entrances = all::<Dungeon>().filter_map(|dungeon| world_dungeon_entrances.get(&dungeon)).collect_vec();
+            let mut targets = entrances.clone();
+            targets.shuffle(rng);
+            for (entrance, target) in entrances.into_iter().zip_eq(targets) {
+                connect(&mut attempt.placements[world], entrance, target);
+            }
+            for &location in locations {
+                let item = attempt.pool[world].iter().copied().filter(|&item| can_hold(location, item)).unique().exactly_one().ok();
+                if let Some(item) = item {
+                    attempt.place(world, location, item);
+                }
+            }
+        }
+        attempt
+    }
+
+    fn place(&mut self, world: usize, location: Location, item: Item) {
+        self.placements[world].items.insert(location, vec![item]);
+        self.empty[world].remove(&location);
+        let idx = self.pool[world].iter().position(|&pool_item| pool_item == item).expect("placed item not in the pool");
+        self.pool[world].swap_remove(idx);
+    }
+
+    /// The root access with every item in the pool as an option for every empty location which can hold it.
+    ///
+    /// Since more options never make a global state unreachable, this overestimates which global states are in logic, so the placements need to be checked again at the end.
+    fn optimistic_region_access<L: Logic<Region = R>>(&self, logic: &L, worlds: &[Settings]) -> Result<Vec<RegionAccess<L>>, search::Error> {
+        let mut placements = self.placements.clone();
+        for ((placement, pool), empty) in placements.iter_mut().zip_eq(&self.pool).zip_eq(&self.empty) {
+            let items = pool.iter().copied().unique().collect_vec();
+            for &location in empty {
+                placement.items.insert(location, items.iter().copied().filter(|&item| can_hold(location, item)).collect());
+            }
+        }
+        search::initial_region_access(logic, worlds, &placements)
+    }
+
+    /// Places the remaining items at random empty locations which can hold them. Returns `false` if an item doesn't fit anywhere.
+    fn fill_remaining(&mut self, rng: &mut impl Rng) -> bool {
+        for world in 0..self.pool.len() {
+            let mut items = self.pool[world].clone();
+            items.shuffle(rng);
+            for item in items {
+                let Some(&location) = self.empty[world].iter().filter(|&&location| can_hold(location, item)).copied().collect_vec().choose(rng) else { return false };
+                self.place(world, location, item);
+            }
+        }
+        true
+    }
+
+    /// The progression items which haven't been placed yet in the given world.
+    fn progression(&self, world: usize) -> impl Iterator<Item = Item> + '_ {
+        self.pool[world].iter().copied().filter(|item| item.class() == ItemClass::Progression)
+    }
+}
+
+/// Makes attempts with new random entrances until `place_progression` succeeds and the remaining items can be placed such that each world can be beaten.
+fn fill_with_restarts<L: Logic, R: Rng>(logic: &L, worlds: &[Settings], rng: &mut R, stats: &mut Stats, max_attempts: NonZeroUsize, mut place_progression: impl FnMut(&mut Attempt<L::Region>, &[RegionAccess<L>], &mut R) -> bool) -> Result<Vec<Placement<L::Region>>, Error> {
+    let infos = logic.regions().into_iter().map(|region| (region, logic.info(region))).collect::<HashMap<_, _>>();
+    let dungeon_entrances = worlds.iter().map(|settings| dungeon_entrances(logic, &infos, settings)).collect::<Result<Vec<_>, _>>()?;
+    let locations = locations(&infos);
+    for _ in 0..max_attempts.get() {
+        let mut attempt = Attempt::new(&locations, &dungeon_entrances, rng);
+        let region_access = attempt.optimistic_region_access(logic, worlds)?;
+        if place_progression(&mut attempt, &region_access, rng) && attempt.fill_remaining(rng) && search::check_reachability(logic, worlds, &attempt.placements).is_ok() {
+            return Ok(attempt.placements)
+        }
+        stats.retries += 1;
+    }
+    Err(Error::Attempts(max_attempts.get()))
+}
+
+/// The assumed fill used by the Python randomizer: progression items are placed one at a time at locations which are reachable assuming all progression items which haven't been placed yet are already collected.
+///
+/// Entrances are shuffled up front without regard for logic, and the fill starts over if the result can't be beaten.
+pub(crate) struct AssumedFill {
+    pub(crate) max_attempts: NonZeroUsize,
+}
+
+impl Strategy for AssumedFill {
+    fn fill<L: Logic>(&self, logic: &L, worlds: &[Settings], rng: &mut impl Rng, stats: &mut Stats) -> Result<Vec<Placement<L::Region>>, Error> {
+        fill_with_restarts(logic, worlds, rng, stats, self.max_attempts, |attempt, region_access, rng| {
+            let mut progression = (0..worlds.len()).flat_map(|world| attempt.progression(world).map(move |item| (world, item))).collect_vec();
+            progression.shuffle(rng);
+            // items which fit in fewer locations go first so they don't run out of space, e.g. songs
+            progression.sort_by_cached_key(|&(world, item)| attempt.empty[world].iter().filter(|&&location| can_hold(location, item)).count());
+            for (idx, &(world, item)) in progression.iter().enumerate() {
+                let mut inventory = Inventory::default();
+                for &(assumed_world, assumed_item) in &progression[idx + 1..] {
+                    inventory.insert(assumed_world, assumed_item);
+                }
+                let reachable = search::reachable_locations(logic, worlds, &attempt.placements, region_access.to_vec(), inventory);
+                let candidates = attempt.empty[world].iter().copied().filter(|&location| can_hold(location, item)).collect_vec();
+                // Not all locations are reachable with the full item pool, but the item might not be required.
+                // If it is, the check once everything is placed fails.
+                let reachable_candidates = candidates.iter().copied().filter(|location| reachable[world].contains(location)).collect_vec();
+                let Some(&location) = (if reachable_candidates.is_empty() { candidates } else { reachable_candidates }).choose(rng) else { return false };
+                attempt.place(world, location, item);
+            }
+            true
+        })
+    }
+}
+
+/// A simple forward fill: progression items are placed one at a time at random locations which are reachable with the items placed so far.
+///
+/// Entrances are shuffled up front without regard for logic, and the fill starts over if the result can't be beaten.
+pub(crate) struct ForwardFill {
+    pub(crate) max_attempts: NonZeroUsize,
+}
+
+impl Strategy for ForwardFill {
+    fn fill<L: Logic>(&self, logic: &L, worlds: &[Settings], rng: &mut impl Rng, stats: &mut Stats) -> Result<Vec<Placement<L::Region>>, Error> {
+        fill_with_restarts(logic, worlds, rng, stats, self.max_attempts, |attempt, region_access, rng| {
+            while (0..worlds.len()).any(|world| attempt.progression(world).next().is_some()) {
+                let reachable = search::reachable_locations(logic, worlds, &attempt.placements, region_access.to_vec(), Inventory::default());
+                let candidates = attempt.empty.iter().enumerate()
+                    .flat_map(|(world, empty)| empty.iter().map(move |&location| (world, location)))
+                    .filter(|&(world, location)| reachable[world].contains(&location) && attempt.progression(world).any(|item| can_hold(location, item)))
+                    .collect_vec();
+                // Once there are no reachable locations left, the remaining progression items are placed along with the other items, since they might not be required.
+                // If they are, the check once everything is placed fails.
+                let Some(&(world, location)) = candidates.choose(rng) else { break };
+                let Some(&item) = attempt.progression(world).filter(|&item| can_hold(location, item)).collect_vec().choose(rng) else { return false };
+                attempt.place(world, location, item);
+            }
+            true
+        })
+    }
+}
+
+/// The combined results of running a fill algorithm with multiple seeds, see [`benchmark`].
+#[derive(Debug)]
+pub(crate) struct BenchmarkResult {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) successes: u32,
+    pub(crate) retries: usize,
+    pub(crate) time: Duration,
+}
+
+/// Runs each fill algorithm with the given number of seeds, finalizing the settings with each seed.
+pub(crate) fn benchmark<L: Logic>(logic: &L, worlds: &[Settings], seeds: NonZeroU32, max_attempts: NonZeroUsize) -> Vec<BenchmarkResult> {
+    all::<Algorithm>().map(|algorithm| {
+        let options = Options { fill_algorithm: algorithm, max_fill_attempts: max_attempts };
+        let mut result = BenchmarkResult { algorithm, successes: 0, retries: 0, time: Duration::default() };
+        for seed in 0..seeds.get() {
+            let mut rng = StdRng::seed_from_u64(seed.into());
+            let mut worlds = worlds.to_vec();
+            finalize_settings(&mut worlds, &mut rng);
+            let mut stats = Stats::default();
+            let start = Instant::now();
+            if options.fill(logic, &worlds, &mut rng, &mut stats).is_ok() {
+                result.successes += 1;
+            }
+            result.time += start.elapsed();
+            result.retries += stats.retries;
+        }
+        result
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        std::collections::HashMap,
+        riirando_common::*,
+        crate::{
+            logic::test_logic::{
+                TestLogic,
+                always,
+            },
+            search::{
+                GlobalState,
+                Inventory,
+            },
+            spoiler,
+        },
+        super::*,
+    };
+
+    const ROOT: u8 = 0;
+    const HYRULE_FIELD: u8 = 1;
+    const GANONDORF_BOSS_ROOM: u8 = 2;
+
+    fn rupee(world: usize, _: &GlobalState<u8>, _: &Settings, inventory: &Inventory<u8>) -> bool { inventory.contains(world, Item::Rupee1) }
+    fn heart(world: usize, _: &GlobalState<u8>, _: &Settings, inventory: &Inventory<u8>) -> bool { inventory.contains(world, Item::RecoveryHeart) }
+    fn rupee_and_heart(world: usize, _: &GlobalState<u8>, _: &Settings, inventory: &Inventory<u8>) -> bool { inventory.contains(world, Item::Rupee1) && inventory.contains(world, Item::RecoveryHeart) }
+    fn sword_and_rupees(world: usize, _: &GlobalState<u8>, _: &Settings, inventory: &Inventory<u8>) -> bool { inventory.contains(world, Item::KokiriSword) && inventory.contains(world, Item::Rupees5) }
+
+    /// A minimal logic with three locations in Hyrule Field. In a single world, only one placement can be beaten:
+    /// the Rupee (1) must be at the location which is always accessible, since the location for the Recovery Heart requires the Rupee (1), and Ganondorf and the last location require both.
+    const TEST_LOGIC: TestLogic = TestLogic {
+        regions: &["Root", "Hyrule Field", "Ganondorf Boss Room"],
+        locations: &[
+            (HYRULE_FIELD, Location::KfMidosTopLeftChest, rupee),
+            (HYRULE_FIELD, Location::KfMidosBottomLeftChest, always),
+            (HYRULE_FIELD, Location::KfMidosBottomRightChest, rupee_and_heart),
+        ],
+        events: &[],
+        exits: &[(ROOT, HYRULE_FIELD, always), (HYRULE_FIELD, GANONDORF_BOSS_ROOM, rupee_and_heart)],
+    };
+
+    /// A logic with dead ends which the search can't detect before everything is placed. The location which is always accessible can hold the Rupee (1) or the Recovery Heart,
+    /// which unlock one location each, but the last location requires both. Each requirement is checked separately, so either item can be assumed for each of them.
+    const DEAD_END_LOGIC: TestLogic = TestLogic {
+        regions: &["Root", "Hyrule Field", "Ganondorf Boss Room"],
+        locations: &[
+            (HYRULE_FIELD, Location::KfKokiriSwordChest, rupee),
+            (HYRULE_FIELD, Location::KfMidosTopLeftChest, heart),
+            (HYRULE_FIELD, Location::KfMidosBottomLeftChest, always),
+            (HYRULE_FIELD, Location::KfMidosBottomRightChest, rupee_and_heart),
+        ],
+        events: &[],
+        exits: &[(ROOT, HYRULE_FIELD, always), (HYRULE_FIELD, GANONDORF_BOSS_ROOM, sword_and_rupees)],
+    };
+
+    /// An RNG which always rolls zero, so the fill always takes the first of its options.
+    struct ZeroRng;
+
+    impl RngCore for ZeroRng {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            dst.fill(0);
+        }
+    }
+
+    /// Asserts that each world has exactly one item at each location, that the items are the world's item pool, and that each world can be beaten.
+    fn assert_complete_and_beatable(logic: &TestLogic, worlds: &[Settings], placements: &[Placement<u8>]) {
+        let infos = logic.regions().into_iter().map(|region| (region, logic.info(region))).collect::<HashMap<_, _>>();
+        let locations = locations(&infos);
+        assert_eq!(placements.len(), worlds.len());
+        for placement in placements {
+            assert!(placement.items.keys().copied().sorted().eq(locations.iter().copied()));
+            assert!(placement.items.values().all(|items| items.len() == 1));
+            assert!(placement.items.values().flatten().copied().sorted().eq(locations.iter().map(|location| location.vanilla_item()).sorted()));
+        }
+        search::check_reachability(logic, worlds, placements).unwrap();
+    }
+
+    #[test]
+    fn fill_is_complete_and_beatable() {
+        let worlds = [Settings::default(), Settings { starting_age: StartingAge::Adult, ..Settings::default() }];
+        for fill_algorithm in all::<Algorithm>() {
+            let options = Options { fill_algorithm, max_fill_attempts: DEFAULT_MAX_ATTEMPTS };
+            for seed in 0..5 {
+                let placements = options.fill(&TEST_LOGIC, &worlds, &mut StdRng::seed_from_u64(seed), &mut Stats::default()).unwrap();
+                assert_complete_and_beatable(&TEST_LOGIC, &worlds, &placements);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_fill_rolls_back_dead_end() {
+        // Taking the first options places the Kokiri Sword and the Rupees (5) at the locations which require the Rupee (1) and the Recovery Heart.
+        // This passes the search, but the always accessible location can only unlock one of them.
+        let worlds = [Settings::default()];
+        let mut stats = Stats::default();
+        let placements = MatrixFill.fill(&DEAD_END_LOGIC, &worlds, &mut ZeroRng, &mut stats).unwrap();
+        assert_eq!(stats.retries, 1);
+        assert_complete_and_beatable(&DEAD_END_LOGIC, &worlds, &placements);
+    }
+
+    #[test]
+    fn undecided_check_gives_one_item() {
+        let placement = |top_left: &[Item], bottom_left: &[Item], bottom_right: &[Item]| Placement::<u8> {
+            items: [
+                (Location::KfMidosTopLeftChest, top_left.to_vec()),
+                (Location::KfMidosBottomLeftChest, bottom_left.to_vec()),
+                (Location::KfMidosBottomRightChest, bottom_right.to_vec()),
+            ].into_iter().collect(),
+            entrances: HashMap::default(),
+        };
+        let worlds = [Settings::default()];
+        search::check_reachability(&TEST_LOGIC, &worlds, &[placement(&[Item::RecoveryHeart, Item::Rupees5], &[Item::Rupee1], &[Item::RecoveryHeart, Item::Rupees5])]).unwrap();
+        // Ganondorf requires both items which the always accessible location could still hold, but it can only hold one of them.
+        assert!(search::check_reachability(&TEST_LOGIC, &worlds, &[placement(&[Item::Rupees5], &[Item::Rupee1, Item::RecoveryHeart], &[Item::Rupee1, Item::RecoveryHeart])]).is_err());
+        // so the matrix fill rejects that placement for the Rupees (5) right away instead of rolling it back later
+        let mut stats = Stats::default();
+        MatrixFill.fill(&TEST_LOGIC, &worlds, &mut ZeroRng, &mut stats).unwrap();
+        assert_eq!(stats.retries, 0);
+    }
+
+    #[test]
+    fn items_are_per_world() {
+        let placement = |top_left, bottom_left| Placement::<u8> {
+            items: [
+                (Location::KfMidosTopLeftChest, vec![top_left]),
+                (Location::KfMidosBottomLeftChest, vec![bottom_left]),
+                (Location::KfMidosBottomRightChest, vec![Item::Rupees5]),
+            ].into_iter().collect(),
+            entrances: HashMap::default(),
+        };
+        let worlds = [Settings::default(), Settings::default()];
+        search::check_reachability(&TEST_LOGIC, &worlds, &[placement(Item::RecoveryHeart, Item::Rupee1), placement(Item::RecoveryHeart, Item::Rupee1)]).unwrap();
+        // The Rupee (1) is locked behind itself in world 1, so that world can't be beaten even though world 2's Rupee (1) is reachable.
+        assert!(search::check_reachability(&TEST_LOGIC, &worlds, &[placement(Item::Rupee1, Item::RecoveryHeart), placement(Item::RecoveryHeart, Item::Rupee1)]).is_err(), "item from world 2 was used in world 1");
+    }
+
+    /// Fills two worlds with the given seed like `main` does, and returns the spoiler log.
+    fn spoiler_log(fill_algorithm: Algorithm, seed: u64) -> String {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut worlds = vec![Settings { starting_age: StartingAge::Random, ..Settings::default() }; 2];
+        finalize_settings(&mut worlds, &mut rng);
+        let placements = Options { fill_algorithm, max_fill_attempts: DEFAULT_MAX_ATTEMPTS }.fill(&TEST_LOGIC, &worlds, &mut rng, &mut Stats::default()).unwrap();
+        let playthrough = search::playthrough(&TEST_LOGIC, &worlds, &placements).unwrap();
+        String::from_utf8(spoiler::to_json(&TEST_LOGIC, seed, &placements, &playthrough).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_spoiler_log() {
+        for fill_algorithm in all::<Algorithm>() {
+            for seed in 0..2 {
+                assert_eq!(spoiler_log(fill_algorithm, seed), spoiler_log(fill_algorithm, seed), "{fill_algorithm:?} with seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Export of the region graph in Graphviz DOT format, for visualizing the logic.
+
+use {
+    std::{
+        collections::BTreeSet,
+        fmt::Write as _,
+        path::Path,
+    },
+    enum_iterator::{
+        Sequence,
+        all,
+    },
+    wheel::fs,
+    riirando_common::*,
+    crate::{
+        logic::Logic,
+        search::RegionAccess,
+    },
+};
+
+/// Escapes a string for use in a quoted DOT ID, with line breaks as Graphviz label line breaks.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// The fill colour for regions with the given savewarp, as an HSV triple with evenly spaced hues.
+fn savewarp_color(savewarp: Savewarp) -> String {
+    let idx = all::<Savewarp>().position(|iter_savewarp| iter_savewarp == savewarp).expect("savewarp not in all::<Savewarp>()");
+    format!("{:.3} 0.300 1.000", idx as f64 / Savewarp::CARDINALITY as f64)
+}
+
+/// The outline colour for regions with the given time of day behavior.
+fn time_of_day_color(time_of_day: TimeOfDayBehavior) -> &'static str {
+    match time_of_day {
+        TimeOfDayBehavior::None => "black",
+        TimeOfDayBehavior::Static => "goldenrod",
+        TimeOfDayBehavior::Passes => "blue",
+        TimeOfDayBehavior::OutsideGanonsCastle => "purple",
+    }
+}
+
+/// Writes the region graph to the given path, with edges labelled by their access expressions.
+///
+/// Regions are filled with a colour for their savewarp and outlined with a colour for their time of day behavior, see the legend in the output.
+/// If `region_access` is given, e.g. from [`crate::search::reachable_regions`], each region is labelled with the ages and times of day it's reachable at, and unreachable regions are drawn dashed.
+pub(crate) async fn export_dot<L: Logic>(logic: &L, region_access: Option<&RegionAccess<L>>, output_path: &Path) -> Result<(), wheel::Error> {
+    let mut regions = logic.regions();
+    regions.sort_by_key(|&region| logic.region_name(region));
+    let mut dot = "digraph logic {\n    node [style=filled, penwidth=3];\n".to_owned();
+    dot.push_str("    subgraph cluster_legend {\n        label=\"Legend\";\n");
+    for savewarp in all::<Savewarp>() {
+        writeln!(dot, "        {} [label={}, fillcolor={}, color=white];", quote(&format!("legend savewarp {savewarp}")), quote(&format!("savewarp: {savewarp}")), quote(&savewarp_color(savewarp))).unwrap();
+    }
+    for time_of_day in all::<TimeOfDayBehavior>() {
+        writeln!(dot, "        {} [label={}, fillcolor=white, color={}];", quote(&format!("legend time of day {time_of_day:?}")), quote(&format!("time of day: {time_of_day:?}")), time_of_day_color(time_of_day)).unwrap();
+    }
+    dot.push_str("    }\n");
+    for &region in &regions {
+        let name = logic.region_name(region);
+        let info = logic.info(region);
+        let mut label = name.to_owned();
+        let mut style = "filled";
+        if let Some(region_access) = region_access {
+            match region_access.get(&region) {
+                Some(states) if !states.is_empty() => {
+                    // savewarps are left out to keep the labels readable
+                    let ages_and_times = states.iter().map(|state| (state.age, state.time_of_day)).collect::<BTreeSet<_>>();
+                    for (age, time_of_day) in ages_and_times {
+                        write!(label, "\n{age:?} {time_of_day:?}").unwrap();
+                    }
+                }
+                _ => style = "filled,dashed",
+            }
+        }
+        writeln!(dot, "    {} [label={}, style={}, fillcolor={}, color={}];", quote(name), quote(&label), quote(style), quote(&savewarp_color(info.savewarp)), time_of_day_color(info.time_of_day)).unwrap();
+        let mut exits = info.exits.into_iter().map(|(target, access)| (logic.region_name(target), logic.access_expr(&access))).collect::<Vec<_>>();
+        exits.sort();
+        for (target, access) in exits {
+            writeln!(dot, "    {} -> {} [label={}];", quote(name), quote(target), quote(&access)).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    fs::write(output_path, dot).await
+}
@@ -0,0 +1,203 @@
+use {
+    std::{
+        collections::HashMap,
+        path::Path,
+        sync::Arc,
+    },
+    riirando_common::{
+        *,
+        ast::{
+            LoadError,
+            LogicSet,
+        },
+    },
+    crate::{
+        logic::{
+            Logic,
+            RegionInfo,
+        },
+        search::{
+            Age,
+            GlobalState,
+            Inventory,
+            TimeOfDay,
+        },
+    },
+};
+
+/// Logic loaded from a directory of logic files at runtime, see `--logic-dir`.
+///
+/// Regions and events are identified by their index in alphabetical order of their names.
+pub(crate) struct InterpretedLogic {
+    root: usize,
+    region_names: Vec<String>,
+    region_indices: HashMap<String, usize>,
+    event_names: Vec<String>,
+    event_indices: HashMap<String, usize>,
+    regions: Vec<RegionInfo<Self>>,
+}
+
+impl InterpretedLogic {
+    pub(crate) fn load(dir: &Path) -> Result<Self, LoadError> {
+        let LogicSet { regions } = LogicSet::load(dir)?;
+        let region_names = regions.keys().cloned().collect::<Vec<_>>();
+        let region_indices = region_names.iter().enumerate().map(|(idx, name)| (name.clone(), idx)).collect::<HashMap<_, _>>();
+        let mut event_names = regions.values().flat_map(|region| region.events.keys().cloned()).collect::<Vec<_>>();
+        event_names.sort();
+        event_names.dedup();
+        let event_indices = event_names.iter().enumerate().map(|(idx, name)| (name.clone(), idx)).collect::<HashMap<_, _>>();
+        let regions = regions.into_values().map(|region| RegionInfo {
+            savewarp: region.savewarp,
+            time_of_day: region.time_of_day,
+            time_travel: region.time_travel,
+            refill: region.refill,
+            farores_wind: region.farores_wind,
+            locations: region.locations.into_iter().map(|(location, rule)| (location, Arc::new(rule))).collect(),
+            events: region.events.into_iter().map(|(event, rule)| (event_indices[&event], Arc::new(rule))).collect(),
+            exits: region.exits.into_iter().map(|(target, rule)| (region_indices[&target], Arc::new(rule))).collect(),
+            damage: region.damage.into_iter().map(|(target, hearts)| (region_indices[&target], hearts)).collect(),
+        }).collect();
+        Ok(Self {
+            // loading validates that the root region exists
+            root: region_indices["Root"],
+            region_names, region_indices, event_names, event_indices, regions,
+        })
+    }
+
+    fn count(&self, count: &Count, settings: &Settings) -> u8 {
+        match count {
+            Count::Literal(count) => *count,
+            Count::Setting(name) => settings.number_setting(name).unwrap_or_default(),
+        }
+    }
+
+    fn eval(&self, rule: &Rule, world: usize, state: &GlobalState<usize>, settings: &Settings, inventory: &Inventory<usize>) -> bool {
+        match rule {
+            Rule::Const(value) => *value,
+            Rule::And(left, right) => self.eval(left, world, state, settings, inventory) && self.eval(right, world, state, settings, inventory),
+            Rule::Or(left, right) => self.eval(left, world, state, settings, inventory) || self.eval(right, world, state, settings, inventory),
+            Rule::Not(rule) => !self.eval(rule, world, state, settings, inventory),
+            Rule::IsAdult => state.age == Age::Adult,
+            Rule::IsChild => state.age == Age::Child,
+            Rule::AtDay => state.time_of_day.is_day(),
+            Rule::AtNight => !state.time_of_day.is_day(),
+            Rule::AtDampeTime => state.time_of_day == TimeOfDay::Dampe,
+            Rule::Item(item, op, count) => op.eval(inventory.count(world, *item), self.count(count, settings)),
+            Rule::CountOf(items, count) => items.iter().filter(|&&item| inventory.contains(world, item)).count() >= usize::from(self.count(count, settings)),
+            Rule::Event(name) => self.event_indices.get(name).is_some_and(|&event| inventory.has_event(world, event)),
+            Rule::Trick(trick) => settings.tricks.contains(trick),
+            Rule::BoolSetting(name) => settings.bool_setting(name) == Some(true),
+            Rule::NumberSetting(name, op, count) => op.eval(settings.number_setting(name).unwrap_or_default(), self.count(count, settings)),
+            Rule::EnumSetting { name, type_name: _, op, variant } => op.eval(settings.enum_setting(name), Some(&**variant)),
+            Rule::Savewarp(savewarp) => state.savewarp == *savewarp,
+        }
+    }
+}
+
+impl Logic for InterpretedLogic {
+    type Region = usize;
+    type Event = usize;
+    type Access = Arc<Rule>;
+
+    fn root(&self) -> usize {
+        self.root
+    }
+
+    fn region(&self, name: &str) -> Option<usize> {
+        self.region_indices.get(name).copied()
+    }
+
+    fn regions(&self) -> Vec<usize> {
+        (0..self.regions.len()).collect()
+    }
+
+    fn region_name(&self, region: usize) -> &str {
+        &self.region_names[region]
+    }
+
+    fn event_name(&self, event: usize) -> &str {
+        &self.event_names[event]
+    }
+
+    fn info(&self, region: usize) -> RegionInfo<Self> {
+        let info = &self.regions[region];
+        RegionInfo {
+            savewarp: info.savewarp,
+            time_of_day: info.time_of_day,
+            time_travel: info.time_travel,
+            refill: info.refill,
+            farores_wind: info.farores_wind,
+            locations: info.locations.clone(),
+            events: info.events.clone(),
+            exits: info.exits.clone(),
+            damage: info.damage.clone(),
+        }
+    }
+
+    fn can_access(&self, access: &Arc<Rule>, world: usize, state: &GlobalState<usize>, settings: &Settings, inventory: &Inventory<usize>) -> bool {
+        self.eval(access, world, state, settings, inventory)
+    }
+
+    fn access_expr(&self, access: &Arc<Rule>) -> String {
+        access.to_string()
+    }
+
+    fn python_rule(&self, access: &Arc<Rule>) -> Option<String> {
+        access.to_python()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        std::{
+            collections::{
+                BTreeMap,
+                BTreeSet,
+            },
+            path::Path,
+        },
+        riirando_common::*,
+        crate::{
+            logic::{
+                CompiledLogic,
+                Logic,
+            },
+            search::{
+                Placement,
+                reachable_regions,
+            },
+        },
+        super::InterpretedLogic,
+    };
+
+    /// Runs the search and returns the reachable states of each region by name, so results from different logic implementations can be compared.
+    fn reachable_region_names<L: Logic>(logic: &L, settings: &Settings) -> BTreeMap<String, BTreeSet<String>> {
+        reachable_regions(logic, std::slice::from_ref(settings), &[Placement::vanilla()]).unwrap()
+            .into_iter()
+            .flatten()
+            .map(|(region, states)| (logic.region_name(region).to_owned(), states.iter().map(|state| format!("{:?}", state.map_regions(|warp_point| logic.region_name(warp_point)))).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn interpreter_matches_compiled() {
+        let interpreted = InterpretedLogic::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/logic"))).unwrap();
+        for settings in [
+            Settings::default(),
+            Settings {
+                open_forest: OpenForest::Open,
+                bridge: Bridge::Medallions,
+                tricks: [Trick::LensWasteland, Trick::ShadowFireArrowEntry].into_iter().collect(),
+                ..Settings::default()
+            },
+            Settings {
+                open_door_of_time: false,
+                starting_age: StartingAge::Adult,
+                ..Settings::default()
+            },
+        ] {
+            assert_eq!(reachable_region_names(&CompiledLogic, &settings), reachable_region_names(&interpreted, &settings), "{settings:?}");
+        }
+    }
+}
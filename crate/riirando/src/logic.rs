@@ -1,22 +1,196 @@
 use {
-    std::collections::HashMap,
+    std::{
+        collections::HashMap,
+        fmt,
+        hash::Hash,
+    },
     collect_mac::collect,
-    enumset::EnumSet,
+    enum_iterator::{
+        Sequence,
+        all,
+    },
     riirando_common::*,
     crate::search::{
         Age,
         GlobalState,
+        Inventory,
         TimeOfDay,
     },
 };
 
-type Access = fn(&GlobalState, &EnumSet<Item>) -> bool;
+/// A source of region data for the search, either compiled in or loaded at runtime.
+pub(crate) trait Logic {
+    type Region: fmt::Debug + Copy + Eq + Hash;
+    type Event: fmt::Debug + Copy + Ord + Hash;
+    type Access;
 
-pub(crate) struct RegionInfo {
+    /// The region the search starts in. Has exits to all savewarps and warp songs.
+    fn root(&self) -> Self::Region;
+    /// Returns the region with the given name as used in the logic files, if it exists.
+    fn region(&self, name: &str) -> Option<Self::Region>;
+    fn regions(&self) -> Vec<Self::Region>;
+    fn region_name(&self, region: Self::Region) -> &str;
+    fn event_name(&self, event: Self::Event) -> &str;
+    fn info(&self, region: Self::Region) -> RegionInfo<Self>;
+    /// Evaluates an access rule in the given world, whose settings and events apply.
+    fn can_access(&self, access: &Self::Access, world: usize, state: &GlobalState<Self::Region>, settings: &Settings, inventory: &Inventory<Self::Event>) -> bool;
+    /// Renders an access rule as an access expression, see [`Rule`]'s `Display` implementation.
+    fn access_expr(&self, access: &Self::Access) -> String;
+    /// Renders an access rule for the Python randomizer, see [`Rule::to_python`].
+    fn python_rule(&self, access: &Self::Access) -> Option<String>;
+}
+
+pub(crate) struct RegionInfo<L: Logic + ?Sized> {
     pub(crate) savewarp: Savewarp,
     pub(crate) time_of_day: TimeOfDayBehavior,
-    pub(crate) items: HashMap<Item, Access>,
-    pub(crate) exits: HashMap<Region, Access>,
+    pub(crate) time_travel: bool,
+    pub(crate) refill: Option<Refill>,
+    /// Whether Farore's Wind can be used in this region.
+    pub(crate) farores_wind: bool,
+    pub(crate) locations: HashMap<Location, L::Access>,
+    pub(crate) events: HashMap<L::Event, L::Access>,
+    pub(crate) exits: HashMap<L::Region, L::Access>,
+    /// The damage in hearts taken when using an exit, for exits which declare it.
+    pub(crate) damage: HashMap<L::Region, u8>,
+}
+
+/// An access rule compiled to a Rust function, along with its renderings which can't be recovered from the function.
+#[derive(Clone, Copy)]
+pub(crate) struct CompiledAccess {
+    can_access: fn(usize, &GlobalState<Region>, &Settings, &Inventory<Event>) -> bool,
+    source: &'static str,
+    python: Option<&'static str>,
+}
+
+/// The logic from `assets/logic`, compiled in using `riirando_macros::regions!`.
+pub(crate) struct CompiledLogic;
+
+impl Logic for CompiledLogic {
+    type Region = Region;
+    type Event = Event;
+    type Access = CompiledAccess;
+
+    fn root(&self) -> Region {
+        Region::Root
+    }
+
+    fn region(&self, name: &str) -> Option<Region> {
+        Region::from_name(name)
+    }
+
+    fn regions(&self) -> Vec<Region> {
+        all().collect()
+    }
+
+    fn region_name(&self, region: Region) -> &str {
+        region.name()
+    }
+
+    fn event_name(&self, event: Event) -> &str {
+        event.name()
+    }
+
+    fn info(&self, region: Region) -> RegionInfo<Self> {
+        region.info()
+    }
+
+    fn can_access(&self, access: &CompiledAccess, world: usize, state: &GlobalState<Region>, settings: &Settings, inventory: &Inventory<Event>) -> bool {
+        (access.can_access)(world, state, settings, inventory)
+    }
+
+    fn access_expr(&self, access: &CompiledAccess) -> String {
+        access.source.to_owned()
+    }
+
+    fn python_rule(&self, access: &CompiledAccess) -> Option<String> {
+        access.python.map(str::to_owned)
+    }
 }
 
 riirando_macros::regions!();
+
+/// A logic for tests which is given as tables, so each test can declare a minimal world.
+#[cfg(test)]
+pub(crate) mod test_logic {
+    use {
+        std::collections::HashMap,
+        riirando_common::*,
+        crate::search::{
+            GlobalState,
+            Inventory,
+        },
+        super::{
+            Logic,
+            RegionInfo,
+        },
+    };
+
+    pub(crate) type TestAccess = fn(usize, &GlobalState<u8>, &Settings, &Inventory<u8>) -> bool;
+
+    pub(crate) fn always(_: usize, _: &GlobalState<u8>, _: &Settings, _: &Inventory<u8>) -> bool { true }
+
+    /// Regions are identified by their index in `regions`, and the first one is the root.
+    ///
+    /// Time can pass and age can be changed anywhere, so all spawn states are in logic.
+    pub(crate) struct TestLogic {
+        pub(crate) regions: &'static [&'static str],
+        /// The locations in each region, as `(region, location, access)`.
+        pub(crate) locations: &'static [(u8, Location, TestAccess)],
+        /// The events in each region, as `(region, event, access)`.
+        pub(crate) events: &'static [(u8, u8, TestAccess)],
+        /// The exits out of each region, as `(from, to, access)`.
+        pub(crate) exits: &'static [(u8, u8, TestAccess)],
+    }
+
+    impl Logic for TestLogic {
+        type Region = u8;
+        type Event = u8;
+        type Access = TestAccess;
+
+        fn root(&self) -> u8 {
+            0
+        }
+
+        fn region(&self, name: &str) -> Option<u8> {
+            self.regions.iter().position(|&region_name| region_name == name).and_then(|idx| idx.try_into().ok())
+        }
+
+        fn regions(&self) -> Vec<u8> {
+            (0..self.regions.len()).map(|idx| idx.try_into().expect("too many test regions")).collect()
+        }
+
+        fn region_name(&self, region: u8) -> &str {
+            self.regions[usize::from(region)]
+        }
+
+        fn event_name(&self, _: u8) -> &str {
+            "Event"
+        }
+
+        fn info(&self, region: u8) -> RegionInfo<Self> {
+            RegionInfo {
+                savewarp: Savewarp::Overworld,
+                time_of_day: TimeOfDayBehavior::Passes,
+                time_travel: true,
+                refill: None,
+                farores_wind: false,
+                locations: self.locations.iter().filter(|&&(location_region, _, _)| location_region == region).map(|&(_, location, access)| (location, access)).collect(),
+                events: self.events.iter().filter(|&&(event_region, _, _)| event_region == region).map(|&(_, event, access)| (event, access)).collect(),
+                exits: self.exits.iter().filter(|&&(from, _, _)| from == region).map(|&(_, to, access)| (to, access)).collect(),
+                damage: HashMap::default(),
+            }
+        }
+
+        fn can_access(&self, access: &TestAccess, world: usize, state: &GlobalState<u8>, settings: &Settings, inventory: &Inventory<u8>) -> bool {
+            access(world, state, settings, inventory)
+        }
+
+        fn access_expr(&self, _: &TestAccess) -> String {
+            String::default()
+        }
+
+        fn python_rule(&self, _: &TestAccess) -> Option<String> {
+            None
+        }
+    }
+}
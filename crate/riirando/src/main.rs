@@ -1,9 +1,23 @@
 use {
     std::{
-        num::NonZeroU8,
-        path::PathBuf,
+        num::{
+            NonZeroU8,
+            NonZeroU32,
+        },
+        path::{
+            Path,
+            PathBuf,
+        },
     },
     crossterm::tty::IsTty as _,
+    rand::{
+        prelude::*,
+        rng,
+    },
+    riirando_common::{
+        Settings,
+        ast::LoadError,
+    },
     tokio::io::{
         AsyncReadExt as _,
         stdin,
@@ -11,20 +25,59 @@ use {
     },
     tokio_util::either::Either,
     wheel::fs::File,
+    crate::{
+        interpret::InterpretedLogic,
+        logic::{
+            CompiledLogic,
+            Logic,
+        },
+        search::Placement,
+    },
 };
 
+mod fill;
+mod graph;
+mod interpret;
 mod logic;
 mod patch;
+mod python;
 mod search;
+mod spoiler;
 
 #[derive(Default, Clone, clap::ValueEnum)]
 enum OutputKind {
+    //TODO make uncompressed ROM the default again once the patcher writes the item overrides and entrance table
+    #[default]
     None,
     Patch,
-    #[default]
     UncompressedRom,
 }
 
+#[derive(clap::Subcommand)]
+enum Subcommand {
+    /// Write the logic to the given directory in the format of the Python randomizer's `data/World` directory, for comparing the two. Doesn't read a base ROM.
+    ExportPython {
+        output_dir: PathBuf,
+    },
+    /// Write the region graph to the given path in Graphviz DOT format. Doesn't read a base ROM.
+    ExportDot {
+        output_path: PathBuf,
+        /// Label each region with the ages and times of day it's reachable at with the default settings, and draw unreachable regions dashed.
+        #[clap(long)]
+        reachability: bool,
+    },
+    /// Run each fill algorithm with the given number of seeds and report its success rate, retries, and time per seed. Doesn't read a base ROM.
+    BenchmarkFill {
+        #[clap(long, default_value = "100")]
+        seeds: NonZeroU32,
+    },
+    /// Convert the Python randomizer's logic from its `data` directory into logic files in the given directory. Rules which can't be translated are marked with `//TODO` comments.
+    ImportPython {
+        python_data_dir: PathBuf,
+        output_dir: PathBuf,
+    },
+}
+
 #[derive(clap::Parser)]
 struct Args {
     /// Read the base ROM from the given path instead of standard input.
@@ -35,17 +88,35 @@ struct Args {
     /// Write the selected output type to the given path instead of standard output. If there's an existing file at that path, it will be overwritten!
     #[clap(short, long)]
     output: Option<PathBuf>,
+    /// Seed for the random number generator. With the same seed, settings, and version, the output is identical. If omitted, a random seed is used, which is recorded in the spoiler log.
+    #[clap(long)]
+    seed: Option<u64>,
     #[clap(short, long, default_value = "1")]
     world_count: NonZeroU8,
     #[clap(short = 'p', long)]
     world: Option<NonZeroU8>,
+    /// Load logic from the given directory at runtime instead of using the logic compiled into the randomizer. Useful for testing logic changes without rebuilding.
+    #[clap(long)]
+    logic_dir: Option<PathBuf>,
+    #[clap(flatten)]
+    fill: fill::Options,
+    /// Write a spoiler log with the playthrough to the given path, in the Python randomizer's JSON format.
+    #[clap(long)]
+    spoiler_log: Option<PathBuf>,
+    #[clap(subcommand)]
+    subcommand: Option<Subcommand>,
 }
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error(transparent)] Decompress(#[from] decompress::Error),
+    #[error(transparent)] Fill(#[from] fill::Error),
     #[error(transparent)] Io(#[from] tokio::io::Error),
+    #[error(transparent)] Logic(#[from] LoadError),
+    #[error(transparent)] Patch(#[from] patch::Error),
+    #[error(transparent)] Python(#[from] python::Error),
     #[error(transparent)] Search(#[from] search::Error),
+    #[error(transparent)] Spoiler(#[from] spoiler::Error),
     #[error(transparent)] Wheel(#[from] wheel::Error),
     #[error("standard input is not a valid OoT 1.0 NTSC ROM")]
     BaseRom,
@@ -57,10 +128,77 @@ enum Error {
     Stdin,
     #[error("standard output is a TTY")]
     Stdout,
+    #[error("the world number to output is greater than the world count")]
+    WorldNumber,
+}
+
+async fn export_dot<L: Logic>(logic: &L, reachability: bool, rng: &mut impl Rng, output_path: &Path) -> Result<(), Error> {
+    let region_access = if reachability {
+        let mut settings = Settings::default();
+        fill::finalize_settings(std::slice::from_mut(&mut settings), rng);
+        search::reachable_regions(logic, &[settings], &[Placement::vanilla()])?.pop()
+    } else {
+        None
+    };
+    graph::export_dot(logic, region_access.as_ref(), output_path).await?;
+    Ok(())
+}
+
+/// Places items and entrances in the worlds, and writes the placements and playthrough to the spoiler log if requested.
+///
+/// The placements are returned with regions by name, so they don't depend on the logic implementation.
+async fn fill_worlds<L: Logic>(logic: &L, seed: u64, rng: &mut impl Rng, worlds: &[Settings], strategy: &impl fill::Strategy, spoiler_log: Option<&Path>) -> Result<Vec<Placement<String>>, Error> {
+    let placements = strategy.fill(logic, worlds, rng, &mut fill::Stats::default())?;
+    if let Some(spoiler_log) = spoiler_log {
+        let playthrough = search::playthrough(logic, worlds, &placements)?;
+        spoiler::write(logic, seed, &placements, &playthrough, spoiler_log).await?;
+    }
+    Ok(placements.into_iter().map(|placement| placement.map_regions(|region| logic.region_name(region).to_owned())).collect())
 }
 
 #[wheel::main]
 async fn main(args: Args) -> Result<(), Error> {
+    // all randomness comes from a single RNG so the output only depends on the seed
+    let seed = args.seed.unwrap_or_else(|| rng().random());
+    let mut rng = StdRng::seed_from_u64(seed);
+    match args.subcommand {
+        Some(Subcommand::ExportPython { output_dir }) => {
+            if let Some(logic_dir) = args.logic_dir {
+                python::export(&InterpretedLogic::load(&logic_dir)?, &output_dir).await?;
+            } else {
+                python::export(&CompiledLogic, &output_dir).await?;
+            }
+            return Ok(())
+        }
+        Some(Subcommand::ExportDot { output_path, reachability }) => {
+            if let Some(logic_dir) = args.logic_dir {
+                export_dot(&InterpretedLogic::load(&logic_dir)?, reachability, &mut rng, &output_path).await?;
+            } else {
+                export_dot(&CompiledLogic, reachability, &mut rng, &output_path).await?;
+            }
+            return Ok(())
+        }
+        Some(Subcommand::BenchmarkFill { seeds }) => {
+            let worlds = vec![Settings::default(); args.world_count.get().into()];
+            let results = if let Some(logic_dir) = args.logic_dir {
+                fill::benchmark(&InterpretedLogic::load(&logic_dir)?, &worlds, seeds, args.fill.max_fill_attempts)
+            } else {
+                fill::benchmark(&CompiledLogic, &worlds, seeds, args.fill.max_fill_attempts)
+            };
+            for fill::BenchmarkResult { algorithm, successes, retries, time } in results {
+                println!("{algorithm:?}: {successes}/{seeds} succeeded, average {:.1} retries and {:.2?} per seed", retries as f64 / f64::from(seeds.get()), time / seeds.get());
+            }
+            return Ok(())
+        }
+        Some(Subcommand::ImportPython { python_data_dir, output_dir }) => {
+            python::import(&python_data_dir, &output_dir).await?;
+            return Ok(())
+        }
+        None => {}
+    }
+    let output_world = args.world.or_else(|| (args.world_count.get() == 1).then_some(NonZeroU8::MIN));
+    // the patcher can't write shuffled items and entrances yet, so fail before spending time on the fill
+    if output_world.is_some() && !matches!(args.output_type, OutputKind::None) { return Err(patch::Error::Placement.into()) }
     let mut input = if let Some(input) = args.input {
         Either::Left(File::open(input).await?)
     } else {
@@ -87,21 +225,35 @@ async fn main(args: Args) -> Result<(), Error> {
         [0xEE, 0x9D, 0x53, 0xB5, 0xBC, 0x01, 0xD0, 0x15] => return Err(Error::PalBaseRom), // PAL (decompressed)
         _ => return Err(Error::BaseRom),
     };
-    let worlds = vec![(); args.world_count.get().into()];
-    //TODO actually randomize stuff
-    search::check_reachability(&worlds)?;
-    let patch = patch::patch_rom(&base_rom);
-    let output = if let Some(output) = args.output {
-        Either::Left(File::create(output).await?)
+    let mut worlds = vec![Settings::default(); args.world_count.get().into()];
+    fill::finalize_settings(&mut worlds, &mut rng);
+    let placements = if let Some(logic_dir) = args.logic_dir {
+        fill_worlds(&InterpretedLogic::load(&logic_dir)?, seed, &mut rng, &worlds, &args.fill, args.spoiler_log.as_deref()).await?
+    } else {
+        fill_worlds(&CompiledLogic, seed, &mut rng, &worlds, &args.fill, args.spoiler_log.as_deref()).await?
+    };
+    let patch = if let Some(output_world) = output_world {
+        let world_idx = usize::from(output_world.get() - 1);
+        let placement = placements.get(world_idx).ok_or(Error::WorldNumber)?;
+        if let OutputKind::None = args.output_type {
+            None
+        } else {
+            Some(patch::patch_rom(&base_rom, placement)?)
+        }
     } else {
-        let stdout = stdout();
-        if stdout.is_tty() { return Err(Error::Stdout) }
-        Either::Right(stdout)
+        None
     };
-    if let Some(_output_world) = args.world.or_else(|| (args.world_count.get() == 1).then_some(NonZeroU8::MIN)) {
+    if let Some(patch) = patch {
+        let output = if let Some(output) = args.output {
+            Either::Left(File::create(output).await?)
+        } else {
+            let stdout = stdout();
+            if stdout.is_tty() { return Err(Error::Stdout) }
+            Either::Right(stdout)
+        };
         match args.output_type {
             OutputKind::None => {}
-            OutputKind::Patch => patch.write_zpf(output).await?,
+            OutputKind::Patch => patch.write_zpf(output, &mut rng).await?,
             OutputKind::UncompressedRom => patch.write_uncompressed_rom(output).await?,
         }
     } else {
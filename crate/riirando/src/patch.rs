@@ -8,22 +8,26 @@ use {
         },
     },
     async_compression::tokio::write::ZlibEncoder,
-    rand::{
-        prelude::*,
-        rng,
-    },
+    rand::prelude::*,
     itertools::Itertools as _,
     tokio::io::{
         self,
         AsyncWrite,
         AsyncWriteExt as _,
     },
+    crate::search::Placement,
 };
 
 const DMADATA_START: u32 = 0x7430;
 const XOR_RANGE: Range<usize> = 0x00b8_ad30..0x00f0_29a0;
 const BLOCK_HEADER_SIZE: usize = 7;
 
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error("writing shuffled items and entrances into the ROM isn't supported yet, use `--output-type=none` to only write the spoiler log")]
+    Placement,
+}
+
 pub(crate) struct Patch<'a> {
     base_rom: &'a [u8],
     /// A list of segments of raw data that should be changed by the patch, identified by their starting addresses and changed data.
@@ -150,14 +154,15 @@ impl<'a> Patch<'a> {
         Ok(())
     }
 
-    pub(crate) async fn write_zpf(&self, writer: impl AsyncWrite + Unpin) -> io::Result<()> {
+    /// Writes the patch in the `.zpf` format. The start address for the XOR key is chosen using the given RNG so the output is reproducible from the seed.
+    pub(crate) async fn write_zpf(&self, writer: impl AsyncWrite + Unpin, rng: &mut impl Rng) -> io::Result<()> {
         let mut zpf_buf = ZlibEncoder::new(writer);
         // header
         zpf_buf.write_all(b"ZPFv1").await?;
         zpf_buf.write_u32(DMADATA_START).await?;
         zpf_buf.write_u32(XOR_RANGE.start.try_into().expect("address out of range")).await?;
         zpf_buf.write_u32(XOR_RANGE.end.try_into().expect("address out of range")).await?;
-        let mut xor_address = rng().random_range(XOR_RANGE);
+        let mut xor_address = rng.random_range(XOR_RANGE);
         zpf_buf.write_u32(xor_address.try_into().expect("address out of range")).await?;
         // DMA updates (none currently)
         zpf_buf.write_u16(0xffff).await?;
@@ -215,7 +220,11 @@ impl<'a> Index<usize> for Patch<'a> {
     }
 }
 
-pub(crate) fn patch_rom(base_rom: &[u8]) -> Patch<'_> {
+pub(crate) fn patch_rom<'a>(base_rom: &'a [u8], placement: &Placement<String>) -> Result<Patch<'a>, Error> {
+    //TODO write the item overrides and entrance table, until then refuse to output a ROM which doesn't match the spoiler log
+    if !placement.is_vanilla() {
+        return Err(Error::Placement)
+    }
     let mut patch = Patch {
         changed_segments: include!(concat!(env!("OUT_DIR"), "/rom-patch.rs")),
         base_rom,
@@ -229,5 +238,5 @@ pub(crate) fn patch_rom(base_rom: &[u8]) -> Patch<'_> {
         let new_bytes = original_bytes.iter().zip_eq(bytes_diff).map(|(original_byte, diff_byte)| original_byte ^ diff_byte).collect_vec();
         patch.write_bytes(write_address, new_bytes);
     }
-    patch
+    Ok(patch)
 }
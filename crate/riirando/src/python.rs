@@ -0,0 +1,526 @@
+//! Conversion between the logic and the Python randomizer's `data/World/*.json` format, so the two can be compared and the remaining Python logic can be ported.
+
+use {
+    std::{
+        collections::{
+            BTreeMap,
+            HashMap,
+        },
+        fmt::Write as _,
+        iter::Peekable,
+        path::{
+            Path,
+            PathBuf,
+        },
+        vec,
+    },
+    convert_case::{
+        Case,
+        Casing as _,
+    },
+    enum_iterator::all,
+    serde::{
+        Deserialize,
+        Serialize as _,
+    },
+    serde_json::{
+        Map,
+        Value,
+        json,
+        ser::{
+            PrettyFormatter,
+            Serializer,
+        },
+    },
+    wheel::fs,
+    riirando_common::{
+        *,
+        ast::savewarp_region,
+    },
+    crate::logic::Logic,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Io(#[from] tokio::io::Error),
+    #[error(transparent)] Json(#[from] serde_json::Error),
+    #[error(transparent)] Wheel(#[from] wheel::Error),
+    #[error("error parsing {}: {source}", path.display())]
+    ParseJson {
+        source: serde_json::Error,
+        path: PathBuf,
+    },
+}
+
+/// Writes the logic to the given directory as one file for the overworld and one for each dungeon, like the Python randomizer's `data/World` directory.
+///
+/// Rules which have no equivalent in the Python randomizer, like the hardcoded savewarp exits from Root, are omitted.
+pub(crate) async fn export<L: Logic>(logic: &L, output_dir: &Path) -> Result<(), Error> {
+    let mut regions = logic.regions();
+    regions.sort_by_key(|&region| logic.region_name(region));
+    let region_names = regions.iter().map(|&region| logic.region_name(region).to_owned()).collect::<Vec<_>>();
+    let mut files = BTreeMap::<String, Vec<Value>>::default();
+    for region in regions {
+        let name = logic.region_name(region);
+        let info = logic.info(region);
+        let savewarp = savewarp_region(info.savewarp, &region_names).filter(|_| info.savewarp != Savewarp::Overworld);
+        let dungeon = savewarp.filter(|_| info.savewarp.is_dungeon());
+        let mut region_json = Map::default();
+        region_json.insert("region_name".to_owned(), json!(name));
+        if let Some(dungeon) = dungeon {
+            region_json.insert("dungeon".to_owned(), json!(dungeon));
+        }
+        if let TimeOfDayBehavior::Passes = info.time_of_day {
+            region_json.insert("time_passes".to_owned(), json!(true));
+        }
+        if let Some(savewarp) = savewarp {
+            region_json.insert("savewarp".to_owned(), json!(savewarp));
+        }
+        let locations = info.locations.iter()
+            .filter_map(|(location, access)| Some((location.name(), logic.python_rule(access)?)))
+            .collect::<BTreeMap<_, _>>();
+        if !locations.is_empty() {
+            region_json.insert("locations".to_owned(), json!(locations));
+        }
+        let events = info.events.iter()
+            .filter_map(|(event, access)| Some((logic.event_name(*event), logic.python_rule(access)?)))
+            .collect::<BTreeMap<_, _>>();
+        if !events.is_empty() {
+            region_json.insert("events".to_owned(), json!(events));
+        }
+        let exits = info.exits.iter()
+            .filter_map(|(target, access)| Some((logic.region_name(*target), logic.python_rule(access)?)))
+            .collect::<BTreeMap<_, _>>();
+        if !exits.is_empty() {
+            region_json.insert("exits".to_owned(), json!(exits));
+        }
+        let file_name = dungeon.cloned().unwrap_or_else(|| "Overworld".to_owned());
+        files.entry(file_name).or_default().push(Value::Object(region_json));
+    }
+    fs::create_dir_all(output_dir).await?;
+    for (file_name, regions) in files {
+        let mut buf = Vec::default();
+        regions.serialize(&mut Serializer::with_formatter(&mut buf, PrettyFormatter::with_indent(b"    ")))?;
+        buf.push(b'\n');
+        fs::write(output_dir.join(format!("{file_name}.json")), buf).await?;
+    }
+    Ok(())
+}
+
+/// Names which riirando's access expressions define themselves, so the Python helpers of the same name aren't imported.
+const BUILTINS: [&str; 11] = ["is_adult", "is_child", "at_day", "at_night", "at_dampe_time", "can_use", "trick", "setting", "has_stones", "has_medallions", "has_dungeon_rewards"];
+
+#[derive(Deserialize)]
+struct PythonRegion {
+    region_name: String,
+    dungeon: Option<String>,
+    #[serde(default)]
+    time_passes: bool,
+    savewarp: Option<String>,
+    #[serde(default)]
+    locations: Map<String, Value>,
+    #[serde(default)]
+    events: Map<String, Value>,
+    #[serde(default)]
+    exits: Map<String, Value>,
+}
+
+/// Reads a JSON file with `#` comments and line breaks inside strings, which the Python randomizer allows in its logic files.
+async fn read_python_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Error> {
+    let text = fs::read_to_string(path).await?;
+    let mut json = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '#' if !in_string => {
+                // keep the line break so line numbers in error messages stay correct
+                if chars.by_ref().any(|c| c == '\n') { json.push('\n') }
+            }
+            '\n' if in_string => json.push(' '),
+            '\\' if in_string => {
+                json.push(c);
+                if let Some(escaped) = chars.next() { json.push(escaped) }
+            }
+            '"' => {
+                in_string = !in_string;
+                json.push(c);
+            }
+            _ => json.push(c),
+        }
+    }
+    serde_json::from_str(&json).map_err(|source| Error::ParseJson { source, path: path.to_owned() })
+}
+
+/// Reads the Python randomizer's `data/World/*.json` and `data/LogicHelpers.json` and writes equivalent logic files to the given directory.
+///
+/// Rules which can't be translated, e.g. because they use Python features or settings riirando doesn't support yet, are replaced with `false` and the original rule is kept in a `//TODO` comment.
+/// The same goes for locations which riirando doesn't know about and savewarps which aren't in [`Savewarp`].
+pub(crate) async fn import(python_data_dir: &Path, output_dir: &Path) -> Result<(), Error> {
+    let python_helpers = read_python_json::<Map<String, Value>>(&python_data_dir.join("LogicHelpers.json")).await?;
+    let mut helper_defs = Vec::default();
+    for (signature, body) in &python_helpers {
+        let (name, params) = match signature.split_once('(') {
+            Some((name, params)) => (name.trim(), params.trim_end_matches(')').split(',').map(|param| param.trim().to_owned()).filter(|param| !param.is_empty()).collect()),
+            None => (signature.trim(), Vec::default()),
+        };
+        if BUILTINS.contains(&name) { continue }
+        helper_defs.push((name.to_owned(), params, python_rule(body)));
+    }
+    // a helper can only be imported if all helpers it uses can be imported, so drop untranslatable helpers until none are left
+    let mut helpers = helper_defs.iter().map(|(name, params, _)| (name.clone(), (helper_name(name), params.len()))).collect::<HashMap<_, _>>();
+    loop {
+        let untranslatable = helper_defs.iter()
+            .filter(|(name, params, body)| helpers.contains_key(name) && Translator { helpers: &helpers, params }.rule(body).is_none())
+            .map(|(name, _, _)| name.clone())
+            .collect::<Vec<_>>();
+        if untranslatable.is_empty() { break }
+        for name in untranslatable {
+            helpers.remove(&name);
+        }
+    }
+    let mut helpers_file = "// Imported from the Python randomizer's LogicHelpers.json\n\n".to_owned();
+    for (name, params, body) in &helper_defs {
+        let signature = format!("{}({})", helper_name(name), params.join(", "));
+        if helpers.contains_key(name) {
+            let body = (Translator { helpers: &helpers, params }).rule(body).expect("checked above");
+            writeln!(helpers_file, "fn {signature} {{ {body} }}").unwrap();
+        } else {
+            writeln!(helpers_file, "//TODO untranslated helper: fn {signature} {{ {body} }}").unwrap();
+        }
+    }
+    let translator = Translator { helpers: &helpers, params: &[] };
+    let mut files = BTreeMap::<PathBuf, String>::default();
+    let mut world_files = tokio::fs::read_dir(python_data_dir.join("World")).await?;
+    let mut world_paths = Vec::default();
+    while let Some(entry) = world_files.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") { world_paths.push(path) }
+    }
+    world_paths.sort();
+    for path in world_paths {
+        let file_stem = path.file_stem().expect("file with extension has a stem").to_string_lossy().into_owned();
+        //TODO import Master Quest dungeons once the logic supports them
+        if file_stem.ends_with(" MQ") { continue }
+        for region in read_python_json::<Vec<PythonRegion>>(&path).await? {
+            let dungeon = region.dungeon.as_deref().and_then(dungeon_savewarp);
+            let output_path = if let Some(dungeon) = dungeon {
+                Path::new("dungeons").join(format!("{}.logic.rs", dungeon.to_string().to_case(Case::Snake)))
+            } else {
+                PathBuf::from(format!("{}.logic.rs", file_stem.to_case(Case::Snake)))
+            };
+            let file = files.entry(output_path).or_default();
+            if !file.is_empty() { file.push('\n') }
+            writeln!(file, "{:?} {{", region.region_name).unwrap();
+            if dungeon.is_none() {
+                // dungeon regions inherit the savewarp from their logic file
+                if let Some(savewarp) = &region.savewarp {
+                    if all::<Savewarp>().any(|known| known.to_string() == savewarp.to_case(Case::Pascal)) {
+                        writeln!(file, "    savewarp: {savewarp:?},").unwrap();
+                    } else {
+                        writeln!(file, "    //TODO savewarp: {savewarp:?},").unwrap();
+                    }
+                }
+            }
+            let time_of_day = if region.time_passes { "Passes" } else if dungeon.is_some() { "None" } else { "Static" };
+            writeln!(file, "    time_of_day: {time_of_day},").unwrap();
+            write_rules(file, "locations", &region.locations, &translator, |name| all::<Location>().any(|location| location.name() == name));
+            write_rules(file, "events", &region.events, &translator, |_| true);
+            write_rules(file, "exits", &region.exits, &translator, |_| true);
+            file.push_str("}\n");
+        }
+    }
+    fs::create_dir_all(output_dir.join("dungeons")).await?;
+    fs::write(output_dir.join("helpers.rs"), helpers_file).await?;
+    for (path, contents) in files {
+        fs::write(output_dir.join(path), contents).await?;
+    }
+    Ok(())
+}
+
+/// The dungeon whose logic file a region with the given Python `dungeon` field belongs in.
+fn dungeon_savewarp(python_dungeon: &str) -> Option<Savewarp> {
+    let name = match python_dungeon {
+        // riirando names dungeons after their entrance region
+        "Ganons Castle" => "Inside Ganons Castle",
+        name => name,
+    }.to_case(Case::Pascal);
+    all::<Savewarp>().find(|savewarp| savewarp.is_dungeon() && savewarp.to_string() == name)
+}
+
+/// Converts a helper name to riirando's convention of Pascal case for item aliases like `Silver_Gauntlets`.
+fn helper_name(python_name: &str) -> String {
+    if python_name.starts_with(char::is_uppercase) { python_name.to_case(Case::Pascal) } else { python_name.to_owned() }
+}
+
+/// Normalizes whitespace in a rule from a JSON value, so it can be kept in a single-line comment.
+fn python_rule(value: &Value) -> String {
+    let rule = value.as_str().map_or_else(|| value.to_string(), str::to_owned);
+    rule.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn write_rules(file: &mut String, key: &str, rules: &Map<String, Value>, translator: &Translator<'_>, is_known: impl Fn(&str) -> bool) {
+    if rules.is_empty() { return }
+    writeln!(file, "    {key}: {{").unwrap();
+    for (name, rule) in rules {
+        let rule = python_rule(rule);
+        if !is_known(name) {
+            writeln!(file, "        //TODO unknown {key} entry {name:?}: {rule}").unwrap();
+        } else if let Some(translated) = translator.rule(&rule) {
+            writeln!(file, "        {name:?}: {translated},").unwrap();
+        } else {
+            writeln!(file, "        {name:?}: false, //TODO untranslated rule: {rule}").unwrap();
+        }
+    }
+    file.push_str("    },\n");
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(u8),
+    Op(CmpOp),
+    LParen,
+    RParen,
+    Comma,
+}
+
+/// Splits a Python rule into tokens, or returns `None` if it uses syntax the importer doesn't handle.
+fn tokenize(rule: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::default();
+    let mut chars = rule.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            '\'' | '"' => {
+                let mut string = String::default();
+                loop {
+                    match chars.next()? {
+                        end if end == c => break,
+                        c => string.push(c),
+                    }
+                }
+                tokens.push(Token::Str(string));
+            }
+            '=' | '!' | '<' | '>' => tokens.push(Token::Op(match (c, chars.next_if_eq(&'=').is_some()) {
+                ('=', true) => CmpOp::Eq,
+                ('!', true) => CmpOp::Ne,
+                ('<', false) => CmpOp::Lt,
+                ('<', true) => CmpOp::Le,
+                ('>', false) => CmpOp::Gt,
+                ('>', true) => CmpOp::Ge,
+                _ => return None,
+            })),
+            '0'..='9' => {
+                let mut number = c.to_string();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) { number.push(digit) }
+                tokens.push(Token::Num(number.parse().ok()?));
+            }
+            c if c == '_' || c.is_alphabetic() => {
+                let mut ident = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c == '_' || c.is_alphanumeric()) { ident.push(c) }
+                tokens.push(Token::Ident(ident));
+            }
+            c if c.is_whitespace() => {}
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+/// The subset of Python expressions used in the Python randomizer's logic files.
+enum PythonExpr {
+    Or(Box<PythonExpr>, Box<PythonExpr>),
+    And(Box<PythonExpr>, Box<PythonExpr>),
+    Not(Box<PythonExpr>),
+    Compare(Box<PythonExpr>, CmpOp, Box<PythonExpr>),
+    Call(String, Vec<PythonExpr>),
+    Tuple(Vec<PythonExpr>),
+    Ident(String),
+    Str(String),
+    Num(u8),
+    Const(bool),
+}
+
+type Tokens = Peekable<vec::IntoIter<Token>>;
+
+fn parse_python_rule(rule: &str) -> Option<PythonExpr> {
+    let mut tokens = tokenize(rule)?.into_iter().peekable();
+    let expr = parse_or(&mut tokens)?;
+    tokens.next().is_none().then_some(expr)
+}
+
+fn keyword(tokens: &mut Tokens, keyword: &str) -> bool {
+    tokens.next_if(|token| matches!(token, Token::Ident(ident) if ident == keyword)).is_some()
+}
+
+fn parse_or(tokens: &mut Tokens) -> Option<PythonExpr> {
+    let mut expr = parse_and(tokens)?;
+    while keyword(tokens, "or") {
+        expr = PythonExpr::Or(Box::new(expr), Box::new(parse_and(tokens)?));
+    }
+    Some(expr)
+}
+
+fn parse_and(tokens: &mut Tokens) -> Option<PythonExpr> {
+    let mut expr = parse_not(tokens)?;
+    while keyword(tokens, "and") {
+        expr = PythonExpr::And(Box::new(expr), Box::new(parse_not(tokens)?));
+    }
+    Some(expr)
+}
+
+fn parse_not(tokens: &mut Tokens) -> Option<PythonExpr> {
+    if keyword(tokens, "not") {
+        Some(PythonExpr::Not(Box::new(parse_not(tokens)?)))
+    } else {
+        let left = parse_atom(tokens)?;
+        Some(if let Some(Token::Op(op)) = tokens.next_if(|token| matches!(token, Token::Op(_))) {
+            PythonExpr::Compare(Box::new(left), op, Box::new(parse_atom(tokens)?))
+        } else {
+            left
+        })
+    }
+}
+
+fn parse_atom(tokens: &mut Tokens) -> Option<PythonExpr> {
+    Some(match tokens.next()? {
+        Token::LParen => {
+            let mut items = vec![parse_or(tokens)?];
+            while tokens.next_if_eq(&Token::Comma).is_some() {
+                items.push(parse_or(tokens)?);
+            }
+            if tokens.next()? != Token::RParen { return None }
+            if items.len() == 1 { items.pop().expect("checked length") } else { PythonExpr::Tuple(items) }
+        }
+        Token::Ident(ident) => match &*ident {
+            "True" => PythonExpr::Const(true),
+            "False" => PythonExpr::Const(false),
+            "and" | "or" | "not" => return None,
+            _ => if tokens.next_if_eq(&Token::LParen).is_some() {
+                let mut args = Vec::default();
+                if tokens.next_if_eq(&Token::RParen).is_none() {
+                    loop {
+                        args.push(parse_or(tokens)?);
+                        match tokens.next()? {
+                            Token::Comma => {}
+                            Token::RParen => break,
+                            _ => return None,
+                        }
+                    }
+                }
+                PythonExpr::Call(ident, args)
+            } else {
+                PythonExpr::Ident(ident)
+            },
+        },
+        Token::Str(string) => PythonExpr::Str(string),
+        Token::Num(number) => PythonExpr::Num(number),
+        Token::Op(_) | Token::RParen | Token::Comma => return None,
+    })
+}
+
+/// How tightly a translated expression binds, to decide where parentheses are needed.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Or,
+    And,
+    Compare,
+    Atom,
+}
+
+/// Translates Python rules to access expressions. Returns `None` for anything without a known equivalent.
+struct Translator<'a> {
+    /// The importable helpers from `LogicHelpers.json`, mapped to their riirando names and numbers of parameters.
+    helpers: &'a HashMap<String, (String, usize)>,
+    /// The parameters of the helper being translated, if any.
+    params: &'a [String],
+}
+
+impl Translator<'_> {
+    fn rule(&self, rule: &str) -> Option<String> {
+        Some(self.expr(&parse_python_rule(rule)?)?.0)
+    }
+
+    fn expr(&self, expr: &PythonExpr) -> Option<(String, Precedence)> {
+        Some(match expr {
+            PythonExpr::Or(left, right) => (format!("{} || {}", self.expr(left)?.0, self.expr(right)?.0), Precedence::Or),
+            PythonExpr::And(left, right) => (format!("{} && {}", self.operand(left, Precedence::And)?, self.operand(right, Precedence::And)?), Precedence::And),
+            PythonExpr::Not(expr) => (format!("!{}", self.operand(expr, Precedence::Atom)?), Precedence::Atom),
+            PythonExpr::Compare(left, op, right) => (self.compare(left, *op, right)?, Precedence::Compare),
+            PythonExpr::Call(name, args) => (self.call(name, args)?, Precedence::Atom),
+            PythonExpr::Tuple(items) => match &**items {
+                [PythonExpr::Ident(item), count] => (format!("{:?}({})", item_from_python(item)?, self.count(count)?), Precedence::Atom),
+                _ => return None,
+            },
+            PythonExpr::Ident(ident) => (self.ident(ident)?, Precedence::Atom),
+            PythonExpr::Str(event) => (format!("{event:?}"), Precedence::Atom),
+            PythonExpr::Const(value) => (value.to_string(), Precedence::Atom),
+            PythonExpr::Num(_) => return None,
+        })
+    }
+
+    fn operand(&self, expr: &PythonExpr, min_precedence: Precedence) -> Option<String> {
+        let (expr, precedence) = self.expr(expr)?;
+        Some(if precedence < min_precedence { format!("({expr})") } else { expr })
+    }
+
+    fn ident(&self, ident: &str) -> Option<String> {
+        if self.params.iter().any(|param| param == ident) || matches!(ident, "is_adult" | "is_child" | "at_day" | "at_night" | "at_dampe_time") {
+            Some(ident.to_owned())
+        } else if let Some(item) = item_from_python(ident) {
+            Some(format!("{item:?}"))
+        } else if let Some(trick) = ident.strip_prefix("logic_").and_then(Trick::from_id) {
+            Some(format!("trick({})", trick.id()))
+        } else if let Some(SettingKind::Bool) = Settings::kind(ident) {
+            Some(format!("setting({ident})"))
+        } else if let Some((name, 0)) = self.helpers.get(ident) {
+            Some(name.clone())
+        } else {
+            None
+        }
+    }
+
+    fn count(&self, count: &PythonExpr) -> Option<String> {
+        match count {
+            PythonExpr::Num(count) => Some(count.to_string()),
+            PythonExpr::Ident(param) if self.params.contains(param) => Some(param.clone()),
+            PythonExpr::Ident(setting) if Settings::kind(setting) == Some(SettingKind::Number) => Some(format!("setting({setting})")),
+            _ => None,
+        }
+    }
+
+    fn compare(&self, left: &PythonExpr, op: CmpOp, right: &PythonExpr) -> Option<String> {
+        let PythonExpr::Ident(setting) = left else { return None };
+        match (Settings::kind(setting)?, right) {
+            (SettingKind::Number, right) => Some(format!("setting({setting}) {} {}", op.symbol(), self.count(right)?)),
+            (SettingKind::Enum { variants, .. }, PythonExpr::Str(value)) if matches!(op, CmpOp::Eq | CmpOp::Ne) => {
+                let variant = variants.iter().find(|variant| variant.to_case(Case::Snake) == *value)?;
+                Some(format!("setting({setting}) {} {variant}", op.symbol()))
+            }
+            _ => None,
+        }
+    }
+
+    fn call(&self, name: &str, args: &[PythonExpr]) -> Option<String> {
+        let args = match (name, args) {
+            ("can_use", [item]) => self.expr(item)?.0,
+            ("has_stones" | "has_medallions" | "has_dungeon_rewards", [count]) => self.count(count)?,
+            _ => {
+                let (name, num_params) = self.helpers.get(name)?;
+                if *num_params != args.len() { return None }
+                let args = args.iter().map(|arg| Some(self.expr(arg)?.0)).collect::<Option<Vec<_>>>()?;
+                return Some(format!("{name}({})", args.join(", ")))
+            }
+        };
+        Some(format!("{name}({args})"))
+    }
+}
+
+/// Looks up an item by its name as written in the Python randomizer's rules, see [`python_item`].
+fn item_from_python(name: &str) -> Option<Item> {
+    all::<Item>().find(|&item| python_item(item) == name)
+}
@@ -1,9 +1,14 @@
 use {
     std::{
+        cell::RefCell,
         collections::{
+            BTreeMap,
+            BTreeSet,
             HashMap,
             HashSet,
         },
+        fmt,
+        hash::Hash,
         ops::Not,
     },
     collect_mac::collect,
@@ -11,19 +16,32 @@ use {
         Sequence,
         all,
     },
-    enumset::EnumSet,
     itertools::Itertools as _,
     petgraph::matrix_graph::DiMatrix,
     riirando_common::*,
-    crate::logic::Region,
+    crate::logic::{
+        Logic,
+        RegionInfo,
+    },
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
 pub(crate) enum Age {
     Child,
     Adult,
 }
 
+impl Age {
+    /// The age the player starts as in the given world, or `None` if the setting is [`StartingAge::Random`] and hasn't been resolved yet.
+    pub(crate) fn starting(settings: &Settings) -> Option<Self> {
+        match settings.starting_age {
+            StartingAge::Child => Some(Self::Child),
+            StartingAge::Adult => Some(Self::Adult),
+            StartingAge::Random => None,
+        }
+    }
+}
+
 impl Not for Age {
     type Output = Self;
 
@@ -35,7 +53,7 @@ impl Not for Age {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Sequence)]
 pub(crate) enum TimeOfDay {
     Noon,
     Dampe,
@@ -54,127 +72,847 @@ impl TimeOfDay {
     //pub(crate) fn is_night(&self) -> bool { !self.is_day() }
 }
 
+/// How much damage the player can take, in whole hearts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct Health {
+    pub(crate) hearts: u8,
+    /// Fairies in bottles, each of which revives the player with [`Health::FAIRY_HEAL`] hearts.
+    pub(crate) fairies: u8,
+}
+
+impl Health {
+    /// The health after loading a save. This is also assumed after savewarping, even though saving and quitting keeps more than 3 hearts.
+    const SPAWN: Self = Self { hearts: 3, fairies: 0 };
+
+    /// The hearts restored by a bottled fairy when it revives the player (0x140 health units in the game), capped at the maximum.
+    const FAIRY_HEAL: u8 = 20;
+
+    /// Whether this is at least as much health as the other.
+    fn covers(&self, other: &Self) -> bool {
+        self.hearts >= other.hearts && self.fairies >= other.fairies
+    }
+
+    /// Returns the health after taking the given damage in hearts, or `None` if the player would die.
+    fn take_damage(mut self, mut damage: u8, max_hearts: u8) -> Option<Self> {
+        while self.hearts <= damage {
+            self.fairies = self.fairies.checked_sub(1)?;
+            damage -= self.hearts;
+            self.hearts = Self::FAIRY_HEAL.min(max_hearts);
+        }
+        self.hearts -= damage;
+        Some(self)
+    }
+}
+
+/// The regions where the player has placed a Farore's Wind warp point, if any.
+///
+/// Each age has its own warp point, which can only be returned to as that age.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct WarpPoints<R> {
+    pub(crate) child: Option<R>,
+    pub(crate) adult: Option<R>,
+}
+
+impl<R: Copy + Eq> WarpPoints<R> {
+    const NONE: Self = Self { child: None, adult: None };
+
+    pub(crate) fn get(&self, age: Age) -> Option<R> {
+        match age {
+            Age::Child => self.child,
+            Age::Adult => self.adult,
+        }
+    }
+
+    /// Returns these warp points with the one for the given age moved to the given region. An existing warp point has to be dispelled first, which is always possible.
+    fn place(self, age: Age, region: R) -> Self {
+        match age {
+            Age::Child => Self { child: Some(region), ..self },
+            Age::Adult => Self { adult: Some(region), ..self },
+        }
+    }
+
+    /// Whether this has all of the other's warp points, since a warp point never makes anything inaccessible.
+    fn covers(&self, other: &Self) -> bool {
+        other.child.is_none_or(|region| self.child == Some(region)) && other.adult.is_none_or(|region| self.adult == Some(region))
+    }
+
+    fn map<T>(self, mut f: impl FnMut(R) -> T) -> WarpPoints<T> {
+        WarpPoints {
+            child: self.child.map(&mut f),
+            adult: self.adult.map(f),
+        }
+    }
+}
+
 /// World state that changes over a seed and is reversible but persists across savewarps.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
-pub(crate) struct GlobalState {
+///
+/// `R` is the type of regions, as used for Farore's Wind warp points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct GlobalState<R> {
     pub(crate) age: Age,
     pub(crate) time_of_day: TimeOfDay,
     pub(crate) savewarp: Savewarp,
-    //TODO health, FW placement?
+    pub(crate) health: Health,
+    pub(crate) farores_wind: WarpPoints<R>,
 }
 
-fn max_explore(region_access: &mut [HashMap<Region, HashSet<GlobalState>>], inventory: &mut EnumSet<Item>) {
-    loop {
-        let mut progress_made = false;
-        for world_region_access in &mut *region_access {
-            for (region, states) in world_region_access.clone() {
-                let info = region.info();
-                for (item, access) in info.items {
-                    if !inventory.contains(item) && states.iter().any(|state| access(state, inventory)) {
-                        inventory.insert(item);
-                        progress_made = true;
-                    }
+impl<R: Copy + Eq> GlobalState<R> {
+    /// The states which the player can spawn in, i.e. all combinations of age, time of day, and savewarp with the health after loading a save and no warp points.
+    fn spawn_states() -> impl Iterator<Item = Self> + Clone {
+        all::<Age>().cartesian_product(all::<TimeOfDay>()).cartesian_product(all::<Savewarp>())
+            .map(|((age, time_of_day), savewarp)| Self { age, time_of_day, savewarp, health: Health::SPAWN, farores_wind: WarpPoints::NONE })
+    }
+
+    /// The state in which Root is reachable from this state, given the savewarp of the current region.
+    ///
+    /// Since Root also represents the ability to savewarp, health is reset to what it is after loading a save. Warp points are kept since they're part of the save file.
+    fn at_root(self, savewarp: Savewarp) -> Self {
+        Self { savewarp, health: Health { hearts: Health::SPAWN.hearts, ..self.health }, ..self }
+    }
+
+    /// Whether this state is the same as the other except for having at least as much health and at least the same warp points.
+    fn covers(&self, other: &Self) -> bool {
+        self.age == other.age && self.time_of_day == other.time_of_day && self.savewarp == other.savewarp && self.health.covers(&other.health) && self.farores_wind.covers(&other.farores_wind)
+    }
+
+    /// Converts the regions in this state's warp points, e.g. to region names.
+    pub(crate) fn map_regions<T>(self, f: impl FnMut(R) -> T) -> GlobalState<T> {
+        GlobalState {
+            age: self.age,
+            time_of_day: self.time_of_day,
+            savewarp: self.savewarp,
+            health: self.health,
+            farores_wind: self.farores_wind.map(f),
+        }
+    }
+}
+
+/// A multiset of collected items, along with the set of events that have happened, for each world.
+///
+/// Progressive items are represented by how many copies have been collected, e.g. 2 progressive hookshots for the longshot.
+///
+/// During the fill, reached checks can still have several possible items. Each of them only gives one item toward a requirement, see [`Inventory::satisfies`].
+/// Outside of that, their items are all counted, as an overestimate.
+#[derive(Debug, Clone)]
+pub(crate) struct Inventory<E> {
+    /// Items are tagged with the world they were found in, since they're only placed within their own world.
+    items: HashMap<(usize, Item), u8>,
+    /// The possible items of reached checks which have more than one, tagged with the check's world.
+    undecided: Vec<(usize, Vec<Item>)>,
+    /// How many of the checks in `undecided` could hold each item.
+    undecided_counts: HashMap<(usize, Item), u8>,
+    /// The items from `undecided` which are counted while checking a requirement, see [`Inventory::satisfies`].
+    assumption: RefCell<Option<Assumption>>,
+    /// Events are tagged with the world they happened in, since they only affect that world.
+    events: HashSet<(usize, E)>,
+}
+
+/// Which items the undecided checks of a world are assumed to give while checking a requirement, see [`Inventory::satisfies`].
+#[derive(Debug, Clone)]
+struct Assumption {
+    world: usize,
+    items: HashMap<Item, u8>,
+    /// The items the requirement has asked for so far.
+    queried: HashSet<Item>,
+}
+
+impl<E> Default for Inventory<E> {
+    fn default() -> Self {
+        Self {
+            items: HashMap::default(),
+            undecided: Vec::default(),
+            undecided_counts: HashMap::default(),
+            assumption: RefCell::default(),
+            events: HashSet::default(),
+        }
+    }
+}
+
+impl<E: Eq + Hash> Inventory<E> {
+    /// How many combinations of items from undecided checks [`Inventory::satisfies`] tries before optimistically assuming that a requirement is met.
+    const MAX_ASSUMPTIONS: usize = 1000;
+
+    pub(crate) fn insert(&mut self, world: usize, item: Item) {
+        let count = self.items.entry((world, item)).or_default();
+        *count = count.saturating_add(1);
+    }
+
+    /// Adds the possible items of a reached check. If there are several, the check still only gives one of them.
+    pub(crate) fn insert_check(&mut self, world: usize, mut items: Vec<Item>) {
+        items.sort();
+        items.dedup();
+        if let Ok(&item) = items.iter().exactly_one() {
+            self.insert(world, item);
+        } else {
+            for &item in &items {
+                let count = self.undecided_counts.entry((world, item)).or_default();
+                *count = count.saturating_add(1);
+            }
+            self.undecided.push((world, items));
+        }
+    }
+
+    pub(crate) fn count(&self, world: usize, item: Item) -> u8 {
+        let decided = self.items.get(&(world, item)).copied().unwrap_or_default();
+        let undecided = match self.assumption.borrow_mut().as_mut() {
+            Some(assumption) if assumption.world == world => {
+                assumption.queried.insert(item);
+                assumption.items.get(&item).copied().unwrap_or_default()
+            }
+            _ => self.undecided_counts.get(&(world, item)).copied().unwrap_or_default(),
+        };
+        decided.saturating_add(undecided)
+    }
+
+    pub(crate) fn contains(&self, world: usize, item: Item) -> bool {
+        self.count(world, item) > 0
+    }
+
+    /// Checks whether the requirement is met in the given world if each undecided check gives only one of its possible items, as described in `assets/doc/fill.md`.
+    ///
+    /// Different requirements may still assume different items from the same check.
+    pub(crate) fn satisfies(&self, world: usize, requirement: impl Fn(&Self) -> bool) -> bool {
+        // Without an assumption, each undecided check gives all of its items. More items never make a requirement harder to meet,
+        // so items from undecided checks only need to be searched if the requirement is met with all of them but not without them.
+        if !requirement(self) { return false }
+        if self.check_assuming(world, &requirement, HashMap::default()).0 { return true }
+        let undecided = self.undecided.iter().filter(|&&(check_world, _)| check_world == world).map(|(_, items)| items.clone()).collect_vec();
+        // giving up is treated like success since the search during the fill is an overestimate anyway
+        self.satisfies_assuming(world, &requirement, &mut HashMap::default(), undecided, &mut 0).unwrap_or(true)
+    }
+
+    /// Searches for items from the undecided checks which meet the requirement, given the items already assumed from other checks.
+    ///
+    /// Returns `None` if the search gives up after [`Self::MAX_ASSUMPTIONS`] steps.
+    fn satisfies_assuming(&self, world: usize, requirement: &impl Fn(&Self) -> bool, assumed: &mut HashMap<Item, u8>, mut undecided: Vec<Vec<Item>>, steps: &mut usize) -> Option<bool> {
+        *steps += 1;
+        if *steps > Self::MAX_ASSUMPTIONS { return None }
+        if self.check_assuming(world, requirement, assumed.clone()).0 { return Some(true) }
+        // if the requirement isn't met with every undecided check giving all of its items, it can't be met at all
+        let mut all_items = assumed.clone();
+        for &item in undecided.iter().flatten() {
+            let count = all_items.entry(item).or_default();
+            *count = count.saturating_add(1);
+        }
+        let (met, queried) = self.check_assuming(world, requirement, all_items);
+        if !met { return Some(false) }
+        let Some(idx) = undecided.iter().position(|items| items.iter().any(|item| queried.contains(item))) else { return Some(true) };
+        let items = undecided.swap_remove(idx);
+        // the check either gives one of the items the requirement asked for…
+        for &item in items.iter().filter(|item| queried.contains(item)) {
+            let count = assumed.entry(item).or_default();
+            *count = count.saturating_add(1);
+            let met = self.satisfies_assuming(world, requirement, assumed, undecided.clone(), steps);
+            let count = assumed.get_mut(&item).expect("assumed item was just inserted");
+            *count -= 1;
+            if met != Some(false) { return met }
+        }
+        // …or one of its other items, which may still be asked for once the requirement gets further
+        let other_items = items.into_iter().filter(|item| !queried.contains(item)).collect_vec();
+        if !other_items.is_empty() {
+            undecided.push(other_items);
+        }
+        self.satisfies_assuming(world, requirement, assumed, undecided, steps)
+    }
+
+    /// Checks the requirement with only the given items from the undecided checks, and returns which items it asked for.
+    fn check_assuming(&self, world: usize, requirement: &impl Fn(&Self) -> bool, items: HashMap<Item, u8>) -> (bool, HashSet<Item>) {
+        *self.assumption.borrow_mut() = Some(Assumption { world, items, queried: HashSet::default() });
+        let met = requirement(self);
+        let assumption = self.assumption.borrow_mut().take().expect("assumption was just set");
+        (met, assumption.queried)
+    }
+
+    pub(crate) fn insert_event(&mut self, world: usize, event: E) {
+        self.events.insert((world, event));
+    }
+
+    pub(crate) fn has_event(&self, world: usize, event: E) -> bool {
+        self.events.contains(&(world, event))
+    }
+
+    /// The player's maximum health in hearts in the given world.
+    pub(crate) fn max_hearts(&self, world: usize) -> u8 {
+        3u8.saturating_add(self.count(world, Item::HeartContainer)).saturating_add(self.count(world, Item::PieceOfHeart) / 4).min(20)
+    }
+
+    /// The number of bottles in the given world which can be emptied to catch fairies.
+    ///
+    /// Big Poes can't be released, so their bottles are excluded.
+    pub(crate) fn fairy_bottles(&self, world: usize) -> u8 {
+        //TODO ensure bottle contents required elsewhere in logic (e.g. Ruto's Letter, blue fire) aren't also counted as fairies
+        [
+            Item::Bottle,
+            Item::BottleWithMilk,
+            Item::BottleWithRedPotion,
+            Item::BottleWithGreenPotion,
+            Item::BottleWithBluePotion,
+            Item::BottleWithFairy,
+            Item::BottleWithFish,
+            Item::BottleWithBlueFire,
+            Item::BottleWithBugs,
+            Item::BottleWithPoe,
+        ].into_iter().map(|item| self.count(world, item)).fold(0, u8::saturating_add)
+    }
+}
+
+/// What the locations and shuffled entrances of a world are filled with.
+///
+/// During the fill, checks which haven't been filled yet have all of their remaining options listed, and the search assumes whichever item each requirement needs (see [`Inventory::satisfies`]) and takes exits to all of their possible targets.
+/// Once the fill is done, each check has exactly one.
+#[derive(Debug, Clone)]
+pub(crate) struct Placement<R> {
+    /// The possible items at each location. Locations without an entry are empty.
+    pub(crate) items: HashMap<Location, Vec<Item>>,
+    /// The possible targets of each shuffled exit, keyed by source region and vanilla target. Exits without an entry lead to their vanilla target.
+    pub(crate) entrances: HashMap<(R, R), Vec<R>>,
+}
+
+impl<R> Placement<R> {
+    /// Every location has its vanilla item and no entrances are shuffled.
+    pub(crate) fn vanilla() -> Self {
+        Self {
+            items: all::<Location>().map(|location| (location, vec![location.vanilla_item()])).collect(),
+            entrances: HashMap::default(),
+        }
+    }
+
+    /// Whether every filled location has its vanilla item and no entrances are shuffled, i.e. whether the placement matches the unrandomized game.
+    pub(crate) fn is_vanilla(&self) -> bool {
+        self.entrances.is_empty() && self.items.iter().all(|(location, items)| *items == [location.vanilla_item()])
+    }
+
+    /// Converts the regions, e.g. to their names so placements from different logic implementations can be handled alike.
+    pub(crate) fn map_regions<T: Eq + Hash>(self, mut f: impl FnMut(R) -> T) -> Placement<T> {
+        Placement {
+            items: self.items,
+            entrances: self.entrances.into_iter().map(|((from, vanilla_target), targets)| ((f(from), f(vanilla_target)), targets.into_iter().map(&mut f).collect())).collect(),
+        }
+    }
+}
+
+/// A location or event collected during a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum Collected<E> {
+    Location {
+        world: usize,
+        location: Location,
+        item: Item,
+    },
+    Event {
+        world: usize,
+        event: E,
+    },
+}
+
+/// The progress of [`explore_regions`] in one world.
+struct Exploration<'a, R> {
+    region_access: &'a mut HashMap<R, HashSet<GlobalState<R>>>,
+    /// The states in `region_access` for each region, age, time of day, and savewarp, so states which cover each other can be found quickly.
+    comparable: HashMap<(R, Age, TimeOfDay, Savewarp), Vec<GlobalState<R>>>,
+    /// Regions whose exits still need to be checked in the given state.
+    queue: Vec<(R, GlobalState<R>)>,
+}
+
+impl<'a, R: Copy + Eq + Hash> Exploration<'a, R> {
+    /// Starts an exploration which checks the exits of all regions in all states they're already reachable in.
+    fn new(region_access: &'a mut HashMap<R, HashSet<GlobalState<R>>>) -> Self {
+        let mut comparable = HashMap::<_, Vec<_>>::default();
+        let mut queue = Vec::default();
+        for (&region, states) in &*region_access {
+            for &state in states {
+                comparable.entry((region, state.age, state.time_of_day, state.savewarp)).or_default().push(state);
+                queue.push((region, state));
+            }
+        }
+        Self { region_access, comparable, queue }
+    }
+
+    /// Marks the region as reachable in the given state, and queues its exits to be checked in that state if that's new.
+    ///
+    /// Since more health or an additional warp point never makes anything inaccessible, only states which aren't covered by others are kept for each combination of age, time of day, and savewarp.
+    fn reach(&mut self, region: R, state: GlobalState<R>) {
+        let comparable = self.comparable.entry((region, state.age, state.time_of_day, state.savewarp)).or_default();
+        if comparable.iter().any(|existing| existing.covers(&state)) { return }
+        let states = self.region_access.entry(region).or_default();
+        comparable.retain(|existing| if state.covers(existing) {
+            states.remove(existing);
+            false
+        } else {
+            true
+        });
+        comparable.push(state);
+        states.insert(state);
+        self.queue.push((region, state));
+    }
+
+    /// Returns the next region and state whose exits need to be checked.
+    fn pop(&mut self) -> Option<(R, GlobalState<R>)> {
+        while let Some((region, state)) = self.queue.pop() {
+            // skip states which were replaced by covering states while in the queue
+            if self.region_access[&region].contains(&state) {
+                return Some((region, state))
+            }
+        }
+        None
+    }
+}
+
+/// Adds the global states in which regions are reachable using exits until no more progress can be made with the current inventory.
+fn explore_regions<L: Logic>(logic: &L, infos: &HashMap<L::Region, RegionInfo<L>>, worlds: &[Settings], placements: &[Placement<L::Region>], region_access: &mut [RegionAccess<L>], inventory: &Inventory<L::Event>) {
+    let root = logic.root();
+    for (world_idx, ((settings, placement), world_region_access)) in worlds.iter().zip_eq(placements).zip_eq(&mut *region_access).enumerate() {
+        let can_play_suns_song = inventory.satisfies(world_idx, |inventory| inventory.contains(world_idx, Item::Ocarina) && inventory.contains(world_idx, Item::SunsSong));
+        let can_cast_farores_wind = inventory.satisfies(world_idx, |inventory| inventory.contains(world_idx, Item::FaroresWind) && inventory.contains(world_idx, Item::MagicMeter));
+        let max_hearts = inventory.max_hearts(world_idx);
+        let fairy_bottles = inventory.fairy_bottles(world_idx);
+        // Since the inventory doesn't change here, each region's exits only need to be checked once for each state the region is reachable in.
+        let mut exploration = Exploration::new(world_region_access);
+        while let Some((region, state)) = exploration.pop() {
+            let info = &infos[&region];
+            // These are checked when taking a state out of the queue rather than when entering the region, so regions reached before finding the required items are included.
+            if info.farores_wind && can_cast_farores_wind {
+                let warp_point = state.farores_wind.get(state.age);
+                if warp_point != Some(region) {
+                    let placed = GlobalState { farores_wind: state.farores_wind.place(state.age, region), ..state };
+                    exploration.reach(region, placed);
+                    exploration.reach(root, placed.at_root(info.savewarp));
+                    // without an existing warp point, the new state covers this one, so its exits are checked when it's taken out of the queue
+                    if warp_point.is_none() { continue }
+                }
+                if let Some(warp_point) = warp_point {
+                    exploration.reach(warp_point, state);
+                    exploration.reach(root, state.at_root(infos[&warp_point].savewarp));
+                }
+            }
+            if info.time_of_day == TimeOfDayBehavior::Static && can_play_suns_song {
+                for time_of_day in [TimeOfDay::Noon, TimeOfDay::Midnight] {
+                    exploration.reach(region, GlobalState { time_of_day, ..state });
+                    exploration.reach(root, GlobalState { time_of_day, ..state }.at_root(info.savewarp));
                 }
-                for (vanilla_target, access) in info.exits {
-                    for state in &states {
-                        if !world_region_access.get(&vanilla_target).is_some_and(|already_reachable_states| already_reachable_states.contains(state)) && access(state, inventory) {
-                            let target_info = vanilla_target.info();
-                            match target_info.time_of_day {
-                                TimeOfDayBehavior::None => {
-                                    world_region_access.entry(vanilla_target).or_default().insert(*state);
-                                    world_region_access.entry(Region::Root).or_default().insert(GlobalState { savewarp: target_info.savewarp, ..*state });
-                                    if let Region::BeyondDoorOfTime = vanilla_target {
-                                        // can time travel here
-                                        let age_change = GlobalState { age: !state.age, ..*state };
-                                        world_region_access.entry(vanilla_target).or_default().insert(age_change);
-                                        world_region_access.entry(Region::Root).or_default().insert(GlobalState { savewarp: target_info.savewarp, ..age_change });
-                                    }
-                                }
-                                TimeOfDayBehavior::Static => {
-                                    //TODO allow setting time to noon or midnight using Sun's Song
-                                    world_region_access.entry(vanilla_target).or_default().insert(*state);
-                                    world_region_access.entry(Region::Root).or_default().insert(GlobalState { savewarp: target_info.savewarp, ..*state });
-                                }
-                                TimeOfDayBehavior::Passes => for time_of_day in all() {
-                                    world_region_access.entry(vanilla_target).or_default().insert(GlobalState { time_of_day, ..*state });
-                                    world_region_access.entry(Region::Root).or_default().insert(GlobalState { savewarp: target_info.savewarp, time_of_day, ..*state });
-                                },
-                                TimeOfDayBehavior::OutsideGanonsCastle => {
-                                    // Time of day outside Ganon's Castle is always Dampé time, but we mark all times of day to avoid an infinite loop from a discrepancy with the check for existing access above.
-                                    // Exits from Ganon's Castle all check for Dampé time to avoid this hack from leaking time of day into the rest of the world.
-                                    for time_of_day in all() {
-                                        world_region_access.entry(vanilla_target).or_default().insert(GlobalState { time_of_day, ..*state });
-                                    }
-                                    world_region_access.entry(Region::Root).or_default().insert(GlobalState { savewarp: target_info.savewarp, time_of_day: TimeOfDay::Dampe, ..*state });
-                                }
+            }
+            if info.time_travel {
+                let age_change = GlobalState { age: !state.age, ..state };
+                exploration.reach(region, age_change);
+                exploration.reach(root, age_change.at_root(info.savewarp));
+            }
+            if let Some(refill) = info.refill {
+                let health = match refill {
+                    Refill::Hearts => Health { hearts: max_hearts, ..state.health },
+                    Refill::Fairies => Health { hearts: max_hearts, fairies: fairy_bottles },
+                };
+                let refilled = GlobalState { health, ..state };
+                exploration.reach(region, refilled);
+                exploration.reach(root, refilled.at_root(info.savewarp));
+            }
+            for (&vanilla_target, access) in &info.exits {
+                let health = match info.damage.get(&vanilla_target) {
+                    Some(&damage) => state.health.take_damage(damage, max_hearts),
+                    None => Some(state.health),
+                };
+                let Some(health) = health else { continue };
+                let state = GlobalState { health, ..state };
+                //TODO undecided exits are taken to all of their possible targets, an overestimate unlike the per-check handling of undecided items in `Inventory::satisfies`
+                let targets = placement.entrances.get(&(region, vanilla_target)).map_or(std::slice::from_ref(&vanilla_target), Vec::as_slice);
+                // the access rule is only checked once it's needed since it's the same for all targets
+                let mut accessible = None;
+                for &target in targets {
+                    if exploration.region_access.get(&target).is_some_and(|already_reachable_states| already_reachable_states.contains(&state)) { continue }
+                    if !*accessible.get_or_insert_with(|| inventory.satisfies(world_idx, |inventory| logic.can_access(access, world_idx, &state, settings, inventory))) { break }
+                    let target_info = &infos[&target];
+                    match target_info.time_of_day {
+                        TimeOfDayBehavior::None => {
+                            exploration.reach(target, state);
+                            exploration.reach(root, state.at_root(target_info.savewarp));
+                        }
+                        TimeOfDayBehavior::Static => {
+                            exploration.reach(target, state);
+                            exploration.reach(root, state.at_root(target_info.savewarp));
+                        }
+                        TimeOfDayBehavior::Passes => for time_of_day in all() {
+                            exploration.reach(target, GlobalState { time_of_day, ..state });
+                            exploration.reach(root, GlobalState { time_of_day, ..state }.at_root(target_info.savewarp));
+                        },
+                        TimeOfDayBehavior::OutsideGanonsCastle => {
+                            // Time of day outside Ganon's Castle is always Dampé time, but we mark all times of day to avoid an infinite loop from a discrepancy with the check for existing access above.
+                            // Exits from Ganon's Castle all check for Dampé time to avoid this hack from leaking time of day into the rest of the world.
+                            for time_of_day in all() {
+                                exploration.reach(target, GlobalState { time_of_day, ..state });
                             }
-                            progress_made = true;
+                            exploration.reach(root, GlobalState { time_of_day: TimeOfDay::Dampe, ..state }.at_root(target_info.savewarp));
                         }
                     }
                 }
             }
         }
-        if !progress_made { break }
+    }
+}
+
+/// Runs the search until no more progress can be made, only collecting the locations and events for which `allowed` returns `true`.
+///
+/// Returns the spheres of the search, i.e. what was collected in each pass, where each pass only uses what was collected in earlier passes.
+fn max_explore<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>], region_access: &mut [RegionAccess<L>], inventory: &mut Inventory<L::Event>, allowed: impl Fn(&Collected<L::Event>) -> bool) -> Vec<Vec<Collected<L::Event>>> {
+    // Region info is looked up once since the search goes over each region many times.
+    let infos = logic.regions().into_iter().map(|region| (region, logic.info(region))).collect::<HashMap<_, _>>();
+    // Since items can be collected multiple times, we need to remember which locations have already been checked.
+    let mut collected_locations = HashSet::<(usize, Location)>::default();
+    let mut spheres = Vec::default();
+    loop {
+        explore_regions(logic, &infos, worlds, placements, region_access, inventory);
+        let mut sphere = Vec::default();
+        let mut sphere_checks = Vec::default();
+        let mut sphere_events = HashSet::<(usize, L::Event)>::default();
+        for (world_idx, ((settings, placement), world_region_access)) in worlds.iter().zip_eq(placements).zip_eq(&*region_access).enumerate() {
+            for (region, states) in world_region_access {
+                let info = &infos[region];
+                for (&location, access) in &info.locations {
+                    if collected_locations.contains(&(world_idx, location)) { continue }
+                    let items = placement.items.get(&location).into_iter().flatten()
+                        .copied()
+                        .filter(|&item| allowed(&Collected::Location { world: world_idx, location, item }))
+                        .collect_vec();
+                    if !items.is_empty() && inventory.satisfies(world_idx, |inventory| states.iter().any(|state| logic.can_access(access, world_idx, state, settings, inventory))) {
+                        collected_locations.insert((world_idx, location));
+                        sphere.extend(items.iter().map(|&item| Collected::Location { world: world_idx, location, item }));
+                        sphere_checks.push((world_idx, location, items));
+                    }
+                }
+                for (&event, access) in &info.events {
+                    let collectable = Collected::Event { world: world_idx, event };
+                    if !inventory.has_event(world_idx, event) && !sphere_events.contains(&(world_idx, event)) && allowed(&collectable) && inventory.satisfies(world_idx, |inventory| states.iter().any(|state| logic.can_access(access, world_idx, state, settings, inventory))) {
+                        sphere_events.insert((world_idx, event));
+                        sphere.push(collectable);
+                    }
+                }
+            }
+        }
+        if sphere.is_empty() { break }
+        // the regions are visited in hash map order, so the sphere is sorted to keep the playthrough reproducible
+        sphere.sort();
+        sphere_checks.sort();
+        for (world, _, items) in sphere_checks {
+            inventory.insert_check(world, items);
+        }
+        for collectable in &sphere {
+            if let Collected::Event { world, event } = *collectable {
+                inventory.insert_event(world, event);
+            }
+        }
+        spheres.push(sphere);
+    }
+    spheres
+}
+
+/// The global states in which each region is reachable in a world.
+pub(crate) type RegionAccess<L> = HashMap<<L as Logic>::Region, HashSet<GlobalState<<L as Logic>::Region>>>;
+
+/// An exit out of a reached region which couldn't be used, see [`Report`].
+#[derive(Debug)]
+pub(crate) struct BlockedExit {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    /// The ages at which the exit was blocked.
+    pub(crate) ages: Vec<Age>,
+    /// The exit's access expression.
+    pub(crate) access: String,
+    /// The damage in hearts taken when using the exit, if any.
+    pub(crate) damage: Option<u8>,
+}
+
+/// A human-readable summary of a world's search result, so logic authors can see why a world is unbeatable.
+#[derive(Debug)]
+pub(crate) struct Report {
+    /// The global states in which each reached region is reachable, by region name and with warp points by region name.
+    pub(crate) reached: BTreeMap<String, BTreeSet<GlobalState<String>>>,
+    /// Exits from reached regions to regions which weren't reached at the same age, since those are where the search got stuck.
+    pub(crate) blocked_exits: Vec<BlockedExit>,
+}
+
+impl Report {
+    fn new<L: Logic>(logic: &L, world: usize, settings: &Settings, placement: &Placement<L::Region>, region_access: &RegionAccess<L>, inventory: &Inventory<L::Event>) -> Self {
+        let max_hearts = inventory.max_hearts(world);
+        let mut blocked_exits = Vec::default();
+        for (&region, states) in region_access {
+            let info = logic.info(region);
+            for (vanilla_target, access) in info.exits {
+                let damage = info.damage.get(&vanilla_target).copied();
+                for &target in placement.entrances.get(&(region, vanilla_target)).map_or(std::slice::from_ref(&vanilla_target), Vec::as_slice) {
+                    let ages = all::<Age>()
+                        .filter(|&age| !region_access.get(&target).is_some_and(|target_states| target_states.iter().any(|state| state.age == age)))
+                        .filter(|&age| states.iter().any(|state| state.age == age && !(
+                            inventory.satisfies(world, |inventory| logic.can_access(&access, world, state, settings, inventory))
+                            && damage.is_none_or(|damage| state.health.take_damage(damage, max_hearts).is_some())
+                        )))
+                        .collect_vec();
+                    if !ages.is_empty() {
+                        blocked_exits.push(BlockedExit {
+                            from: logic.region_name(region).to_owned(),
+                            to: logic.region_name(target).to_owned(),
+                            access: logic.access_expr(&access),
+                            ages, damage,
+                        });
+                    }
+                }
+            }
+        }
+        blocked_exits.sort_by(|exit1, exit2| (&exit1.from, &exit1.to).cmp(&(&exit2.from, &exit2.to)));
+        Self {
+            reached: region_access.iter().map(|(&region, states)| (logic.region_name(region).to_owned(), states.iter().map(|state| state.map_regions(|warp_point| logic.region_name(warp_point).to_owned())).collect())).collect(),
+            blocked_exits,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reached regions:")?;
+        for (region, states) in &self.reached {
+            let mut savewarps = BTreeMap::<_, BTreeSet<_>>::default();
+            for state in states {
+                savewarps.entry((state.age, state.time_of_day)).or_default().insert(state.savewarp);
+            }
+            // group ages and times of day which have the same savewarps to keep the list short
+            let mut ages_and_times = BTreeMap::<_, BTreeMap<_, Vec<_>>>::default();
+            for ((age, time_of_day), savewarps) in savewarps {
+                ages_and_times.entry(savewarps).or_default().entry(age).or_default().push(time_of_day);
+            }
+            write!(f, "\n    {region}: {}", ages_and_times.into_iter().map(|(savewarps, ages_and_times)| format!(
+                "{} (savewarps: {})",
+                ages_and_times.into_iter().map(|(age, times_of_day)| format!("{age:?} at {}", times_of_day.into_iter().map(|time_of_day| format!("{time_of_day:?}")).join("/"))).join(", "),
+                savewarps.into_iter().join(", "),
+            )).join("; "))?;
+        }
+        if self.blocked_exits.is_empty() {
+            write!(f, "\nno blocked exits")
+        } else {
+            write!(f, "\nblocked exits:")?;
+            for BlockedExit { from, to, ages, access, damage } in &self.blocked_exits {
+                write!(f, "\n    {from} -> {to} as {}: {access}", ages.iter().map(|age| format!("{age:?}")).join(" or "))?;
+                if let Some(damage) = damage {
+                    write!(f, " (deals {damage} damage)")?;
+                }
+            }
+            Ok(())
+        }
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
-    #[error("at least one world has no access to Hyrule Field as child, which is required to collect Zelda's Lullaby, which is required to beat the Shadow temple")]
-    ChildHyruleFieldAccess(HashMap<Region, HashSet<GlobalState>>),
-    #[error("at least one world has no access to Ganondorf's boss room as adult")]
-    AdultGanondorfBossRoomAccess,
+    #[error("world {world} has no access to Hyrule Field as child, which is required to collect Zelda's Lullaby, which is required to beat the Shadow temple\n{report}")]
+    ChildHyruleFieldAccess {
+        world: usize,
+        report: Report,
+    },
+    #[error("world {world} has no access to Ganondorf's boss room as adult\n{report}")]
+    AdultGanondorfBossRoomAccess {
+        world: usize,
+        report: Report,
+    },
+    #[error("the logic has no region named {0:?}")]
+    MissingRegion(&'static str),
+    #[error("world {world} has a random starting age which wasn't resolved before the search")]
+    UnresolvedStartingAge {
+        world: usize,
+    },
 }
 
-/// Returns an error if the reachability requirements as defined in the settings aren't met.
-pub(crate) fn check_reachability(worlds: &[()]) -> Result<(), Error> {
+/// Returns the starting age of each world, or an error if a world's settings haven't been finalized.
+pub(crate) fn starting_ages(worlds: &[Settings]) -> Result<Vec<Age>, Error> {
+    worlds.iter().enumerate().map(|(world_idx, settings)| Age::starting(settings).ok_or(Error::UnresolvedStartingAge { world: world_idx + 1 })).collect()
+}
+
+/// Returns the global states in which each region is reachable in each world with the given placements.
+pub(crate) fn reachable_regions<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>]) -> Result<Vec<RegionAccess<L>>, Error> {
+    let mut region_access = initial_region_access(logic, worlds, placements)?;
+    max_explore(logic, worlds, placements, &mut region_access, &mut Inventory::default(), |_| true);
+    Ok(region_access)
+}
+
+/// Returns the root region's access in each world before anything is collected, i.e. the global states which the search considers in logic.
+pub(crate) fn initial_region_access<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>]) -> Result<Vec<RegionAccess<L>>, Error> {
     // We only consider global states in logic if they're reachable from all other global states.
     // This way, even if a player reaches a global state out of logic, they can't get stuck.
     // To avoid a combinatorial explosion, we require each world to do so without outside help.
-    let reachable_states = worlds.into_iter().map(|()| {
-        let mut reachability_graph = DiMatrix::<_, _>::with_capacity(GlobalState::CARDINALITY);
-        let node_indices = all::<GlobalState>().map(|state| reachability_graph.add_node(state)).collect_vec();
-        for (from_idx, from) in all::<GlobalState>().enumerate() {
+    // Spawn states with warp points don't need to be checked since they cover the same states without warp points.
+    let starting_ages = starting_ages(worlds)?;
+    let reachable_states = worlds.iter().zip_eq(placements).zip_eq(starting_ages).map(|((settings, placement), starting_age)| {
+        let spawn_states = GlobalState::spawn_states();
+        let mut reachability_graph = DiMatrix::<_, _>::with_capacity(spawn_states.clone().count());
+        let node_indices = spawn_states.clone().map(|state| reachability_graph.add_node(state)).collect_vec();
+        for (from_idx, from) in spawn_states.clone().enumerate() {
             let mut assumed_access = HashMap::default();
-            for (to_idx, to) in all().enumerate() {
+            for (to_idx, to) in spawn_states.clone().enumerate() {
                 if petgraph::algo::has_path_connecting(&reachability_graph, node_indices[from_idx], node_indices[to_idx], None) {
                     // already known to be transitively reachable, don't need to check for direct reachability
                     continue
                 }
                 // check whether the target state is reachable from the source state
                 if assumed_access.is_empty() {
-                    assumed_access.insert(Region::Root, collect![from]);
-                    max_explore(std::slice::from_mut(&mut assumed_access), &mut EnumSet::default());
+                    assumed_access.insert(logic.root(), collect![from]);
+                    max_explore(logic, std::slice::from_ref(settings), std::slice::from_ref(placement), std::slice::from_mut(&mut assumed_access), &mut Inventory::default(), |_| true);
                 }
-                if assumed_access.get(&Region::Root).is_some_and(|states| states.contains(&to)) {
+                if assumed_access.get(&logic.root()).is_some_and(|states| states.iter().any(|state| state.covers(&to))) {
                     reachability_graph.add_edge(node_indices[from_idx], node_indices[to_idx], ());
                 }
             }
         }
         let mut dfs_space = petgraph::algo::DfsSpace::new(&reachability_graph);
-        all::<GlobalState>()
+        spawn_states
             .enumerate()
+            .filter(|&(_, to)| to.age == starting_age)
             .filter(|&(to_idx, _)| node_indices.iter().all(|&from_idx| petgraph::algo::has_path_connecting(&reachability_graph, from_idx, node_indices[to_idx], Some(&mut dfs_space))))
             .map(|(_, to)| to)
             .collect::<HashSet<_>>()
     });
-    // The root region is reachable as all states which were proven reachable above.
-    let mut region_access = reachable_states
-        .map(|world_reachable_states| collect![as HashMap<_, _>: Region::Root => world_reachable_states])
-        .collect_vec();
-    // Now we start the real search.
-    max_explore(&mut region_access, &mut EnumSet::default() /*TODO keep this parameter to check for items required to beat the game */);
-    // Search completed, check if we can beat the game.
-    for world_region_access in region_access {
+    // The root region is reachable as all states of the starting age which were proven reachable above.
+    Ok(reachable_states
+        .map(|world_reachable_states| collect![as HashMap<_, _>: logic.root() => world_reachable_states])
+        .collect())
+}
+
+/// Returns an error if the reachability requirements as defined in the settings aren't met.
+pub(crate) fn check_reachability<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>]) -> Result<(), Error> {
+    let mut region_access = initial_region_access(logic, worlds, placements)?;
+    let mut inventory = Inventory::default();
+    max_explore(logic, worlds, placements, &mut region_access, &mut inventory, |_| true);
+    check_win(logic, worlds, placements, &region_access, &inventory)
+}
+
+/// Returns the locations in each world which can be checked starting from the given root access and inventory, including empty ones.
+///
+/// This is used by fill strategies which need to know where an item can be placed.
+pub(crate) fn reachable_locations<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>], mut region_access: Vec<RegionAccess<L>>, mut inventory: Inventory<L::Event>) -> Vec<HashSet<Location>> {
+    max_explore(logic, worlds, placements, &mut region_access, &mut inventory, |_| true);
+    worlds.iter().zip_eq(&region_access).enumerate().map(|(world_idx, (settings, world_region_access))| {
+        world_region_access.iter().flat_map(|(&region, states)| {
+            logic.info(region).locations.into_iter()
+                .filter(|(_, access)| inventory.satisfies(world_idx, |inventory| states.iter().any(|state| logic.can_access(access, world_idx, state, settings, inventory))))
+                .map(|(location, _)| location)
+        }).collect()
+    }).collect()
+}
+
+/// Returns an error if the game can't be beaten in each world with the given search result.
+fn check_win<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>], region_access: &[RegionAccess<L>], inventory: &Inventory<L::Event>) -> Result<(), Error> {
+    let hyrule_field = logic.region("Hyrule Field").ok_or(Error::MissingRegion("Hyrule Field"))?;
+    let ganondorf_boss_room = logic.region("Ganondorf Boss Room").ok_or(Error::MissingRegion("Ganondorf Boss Room"))?;
+    for (world_idx, ((settings, placement), world_region_access)) in worlds.iter().zip_eq(placements).zip_eq(region_access).enumerate() {
         //TODO different win conditions, e.g. ALR, no logic, Triforce Hunt, Bingo
         // needs to be child to collect Zelda's Lullaby, which is required to beat the Shadow temple
-        if !world_region_access.get(&Region::HyruleField).is_some_and(|states| states.iter().any(|state| state.age == Age::Child)) {
-            return Err(Error::ChildHyruleFieldAccess(world_region_access))
+        if !world_region_access.get(&hyrule_field).is_some_and(|states| states.iter().any(|state| state.age == Age::Child)) {
+            return Err(Error::ChildHyruleFieldAccess { world: world_idx + 1, report: Report::new(logic, world_idx, settings, placement, world_region_access, inventory) })
         }
         // needs to be able to reach Ganon
-        if !world_region_access.get(&Region::GanondorfBossRoom).is_some_and(|states| states.iter().any(|state| state.age == Age::Adult)) { //TODO check for items required to defeat Ganon (including sword, in preparation for Master Sword shuffle)
-            return Err(Error::AdultGanondorfBossRoomAccess)
+        if !world_region_access.get(&ganondorf_boss_room).is_some_and(|states| states.iter().any(|state| state.age == Age::Adult)) { //TODO check for items required to defeat Ganon (including sword, in preparation for Master Sword shuffle)
+            return Err(Error::AdultGanondorfBossRoomAccess { world: world_idx + 1, report: Report::new(logic, world_idx, settings, placement, world_region_access, inventory) })
         }
     }
     Ok(())
 }
+
+/// The result of [`playthrough`].
+pub(crate) struct Playthrough<E> {
+    /// Everything collected by the search, by sphere.
+    pub(crate) spheres: Vec<Vec<Collected<E>>>,
+    /// The spheres of a search which only collects a minimal set of locations and events required to beat the game, as listed in the Python randomizer's spoiler log.
+    pub(crate) required_spheres: Vec<Vec<Collected<E>>>,
+}
+
+/// Runs the search sphere by sphere and finds the locations and events required to beat the game, or returns an error if it can't be beaten.
+pub(crate) fn playthrough<L: Logic>(logic: &L, worlds: &[Settings], placements: &[Placement<L::Region>]) -> Result<Playthrough<L::Event>, Error> {
+    let initial_region_access = initial_region_access(logic, worlds, placements)?;
+    let search = |allowed: &dyn Fn(&Collected<L::Event>) -> bool| {
+        let mut region_access = initial_region_access.clone();
+        let mut inventory = Inventory::default();
+        let spheres = max_explore(logic, worlds, placements, &mut region_access, &mut inventory, allowed);
+        (spheres, check_win(logic, worlds, placements, &region_access, &inventory))
+    };
+    let (spheres, result) = search(&|_| true);
+    result?;
+    // Leave out each collected location or event in turn, starting with the last sphere, and keep it out if the game can still be beaten without it.
+    let mut required = spheres.iter().flatten().copied().collect::<HashSet<_>>();
+    for collectable in spheres.iter().rev().flat_map(|sphere| sphere.iter().rev()) {
+        required.remove(collectable);
+        if search(&|collectable| required.contains(collectable)).1.is_err() {
+            required.insert(*collectable);
+        }
+    }
+    let (required_spheres, _) = search(&|collectable| required.contains(collectable));
+    Ok(Playthrough { spheres, required_spheres })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        riirando_common::*,
+        crate::logic::{
+            CompiledLogic,
+            Event,
+            test_logic::{
+                TestLogic,
+                always,
+            },
+        },
+        super::*,
+    };
+
+    const ROOT: u8 = 0;
+    const START: u8 = 1;
+    const A: u8 = 2;
+    const B: u8 = 3;
+    const EVENT: u8 = 0;
+
+    fn door_of_time_open(_: usize, _: &GlobalState<u8>, settings: &Settings, _: &Inventory<u8>) -> bool { settings.open_door_of_time }
+    fn event(world: usize, _: &GlobalState<u8>, _: &Settings, inventory: &Inventory<u8>) -> bool { inventory.has_event(world, EVENT) }
+
+    /// A minimal logic where an event in region A is required to enter region B, and A can only be entered if the Door of Time is open.
+    const TEST_LOGIC: TestLogic = TestLogic {
+        regions: &["Root", "Start", "A", "B"],
+        locations: &[],
+        events: &[(A, EVENT, always)],
+        exits: &[(ROOT, START, always), (START, A, door_of_time_open), (START, B, event)],
+    };
+
+    #[test]
+    fn events_are_per_world() {
+        let worlds = [
+            Settings::default(),
+            Settings { open_door_of_time: false, ..Settings::default() },
+        ];
+        let region_access = reachable_regions(&TEST_LOGIC, &worlds, &[Placement::vanilla(), Placement::vanilla()]).unwrap();
+        assert!(region_access[0].contains_key(&B));
+        assert!(!region_access[1].contains_key(&A));
+        assert!(!region_access[1].contains_key(&B), "event from world 1 was used in world 2");
+    }
+
+    #[test]
+    fn fairy_heal_is_capped() {
+        let health = Health { hearts: 2, fairies: 1 };
+        assert_eq!(health.take_damage(3, 30), Some(Health { hearts: 19, fairies: 0 }));
+        assert_eq!(health.take_damage(3, 5), Some(Health { hearts: 4, fairies: 0 }));
+        assert_eq!(health.take_damage(3, 30).and_then(|health| health.take_damage(19, 30)), None);
+    }
+
+    #[test]
+    fn required_spheres_are_minimal() {
+        let worlds = [Settings::default()];
+        let placements = [Placement::vanilla()];
+        let playthrough = playthrough(&CompiledLogic, &worlds, &placements).unwrap();
+        let initial_region_access = initial_region_access(&CompiledLogic, &worlds, &placements).unwrap();
+        let can_win = |allowed: &HashSet<Collected<Event>>| {
+            let mut region_access = initial_region_access.clone();
+            let mut inventory = Inventory::default();
+            max_explore(&CompiledLogic, &worlds, &placements, &mut region_access, &mut inventory, |collectable| allowed.contains(collectable));
+            check_win(&CompiledLogic, &worlds, &placements, &region_access, &inventory).is_ok()
+        };
+        let required = playthrough.required_spheres.iter().flatten().copied().collect::<HashSet<_>>();
+        assert!(required.len() < playthrough.spheres.iter().flatten().count(), "nothing was left out of the playthrough");
+        assert!(can_win(&required));
+        for collectable in &required {
+            let mut without = required.clone();
+            without.remove(collectable);
+            assert!(!can_win(&without), "{collectable:?} is in the playthrough but not required");
+        }
+    }
+}
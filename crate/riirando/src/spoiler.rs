@@ -0,0 +1,136 @@
+//! The spoiler log, written in the Python randomizer's JSON format so existing tools can read it.
+
+use {
+    std::{
+        collections::BTreeMap,
+        path::Path,
+    },
+    itertools::Itertools as _,
+    serde::Serialize as _,
+    serde_json::{
+        Map,
+        Value,
+        json,
+        ser::{
+            PrettyFormatter,
+            Serializer,
+        },
+    },
+    wheel::fs,
+    crate::{
+        logic::Logic,
+        search::{
+            Collected,
+            Placement,
+            Playthrough,
+        },
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Json(#[from] serde_json::Error),
+    #[error(transparent)] Wheel(#[from] wheel::Error),
+}
+
+/// Renders the locations and events collected in a sphere, or returns `None` if there's nothing to render.
+fn sphere_json<L: Logic>(logic: &L, world_count: usize, sphere: &[Collected<L::Event>], include_events: bool) -> Option<Map<String, Value>> {
+    let mut locations = Vec::default();
+    let mut events = Vec::default();
+    for collected in sphere {
+        match *collected {
+            Collected::Location { world, location, item } => locations.push((world, location, item)),
+            Collected::Event { world, event } => if include_events { events.push((world, logic.event_name(event))) },
+        }
+    }
+    if locations.is_empty() && events.is_empty() { return None }
+    locations.sort();
+    events.sort();
+    let mut sphere_json = Map::default();
+    for (world, location, item) in locations {
+        if world_count == 1 {
+            sphere_json.insert(location.name().to_owned(), json!(item.name()));
+        } else {
+            sphere_json.insert(format!("{} [W{}]", location.name(), world + 1), json!({
+                "item": item.name(),
+                "player": world + 1,
+            }));
+        }
+    }
+    for (world, event) in events {
+        // the Python randomizer's item type for events
+        if world_count == 1 {
+            sphere_json.insert(event.to_owned(), json!("Event"));
+        } else {
+            sphere_json.insert(format!("{event} [W{}]", world + 1), json!("Event"));
+        }
+    }
+    Some(sphere_json)
+}
+
+/// Renders spheres as an object keyed by sphere number, skipping empty spheres.
+fn spheres_json<L: Logic>(logic: &L, world_count: usize, spheres: &[Vec<Collected<L::Event>>], include_events: bool) -> Map<String, Value> {
+    spheres.iter()
+        .filter_map(|sphere| sphere_json(logic, world_count, sphere, include_events))
+        .enumerate()
+        .map(|(sphere_idx, sphere)| (sphere_idx.to_string(), Value::Object(sphere)))
+        .collect()
+}
+
+/// Renders the items at all locations and the targets of all shuffled entrances of a world.
+fn placement_json<L: Logic>(logic: &L, world: usize, world_count: usize, placement: &Placement<L::Region>) -> (Map<String, Value>, Map<String, Value>) {
+    let locations = placement.items.iter()
+        .map(|(location, items)| (location, items.iter().map(|item| item.name()).join(" or ")))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(location, item)| (location.name().to_owned(), if world_count == 1 { json!(item) } else { json!({
+            "item": item,
+            "player": world + 1,
+        }) }))
+        .collect();
+    let entrances = placement.entrances.iter()
+        .map(|(&(from, vanilla_target), targets)| (
+            format!("{} -> {}", logic.region_name(from), logic.region_name(vanilla_target)),
+            json!(targets.iter().map(|&target| logic.region_name(target)).join(" or ")),
+        ))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .collect();
+    (locations, entrances)
+}
+
+/// Renders a spoiler log with the seed, placements, and playthrough as pretty-printed JSON.
+///
+/// Like in the Python randomizer, `:playthrough` only lists the locations required to beat the game, with spheres that only have events in them skipped.
+/// `:full_playthrough` additionally lists everything the search collected, including events.
+/// In multiworld, locations are suffixed with their world number and items are listed along with the player they're for,
+/// and the placements are grouped by world.
+pub(crate) fn to_json<L: Logic>(logic: &L, seed: u64, placements: &[Placement<L::Region>], playthrough: &Playthrough<L::Event>) -> Result<Vec<u8>, Error> {
+    let world_count = placements.len();
+    let (locations, entrances) = if world_count == 1 {
+        placement_json(logic, 0, world_count, &placements[0])
+    } else {
+        placements.iter().enumerate().map(|(world, placement)| {
+            let (locations, entrances) = placement_json(logic, world, world_count, placement);
+            ((format!("World {}", world + 1), Value::Object(locations)), (format!("World {}", world + 1), Value::Object(entrances)))
+        }).unzip()
+    };
+    let spoiler = json!({
+        // the Python randomizer's seeds are strings
+        ":seed": seed.to_string(),
+        "locations": locations,
+        "entrances": entrances,
+        ":playthrough": spheres_json(logic, world_count, &playthrough.required_spheres, false),
+        ":full_playthrough": spheres_json(logic, world_count, &playthrough.spheres, true),
+    });
+    let mut buf = Vec::default();
+    spoiler.serialize(&mut Serializer::with_formatter(&mut buf, PrettyFormatter::with_indent(b"\t")))?;
+    buf.push(b'\n');
+    Ok(buf)
+}
+
+/// Writes a spoiler log with the seed, placements, and playthrough to the given path, see [`to_json`].
+pub(crate) async fn write<L: Logic>(logic: &L, seed: u64, placements: &[Placement<L::Region>], playthrough: &Playthrough<L::Event>, path: &Path) -> Result<(), Error> {
+    fs::write(path, to_json(logic, seed, placements, playthrough)?).await?;
+    Ok(())
+}